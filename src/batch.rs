@@ -0,0 +1,61 @@
+use chrono::prelude::*;
+use std::collections::HashMap;
+
+use crate::{fiscal_quarter_of_month, quarter_boundaries};
+
+/// Returns the calendar quarter (1-4) of `date` without building a full set of
+/// coordinates.
+pub fn quarter_of(date: &impl Datelike) -> u32 {
+    fiscal_quarter_of_month(date.month() as u8, 1) as u32
+}
+
+/// Caches the quarter boundaries of each year seen, for computing bounds over
+/// large batches of timestamps.
+#[derive(Default)]
+pub struct QuarterBoundsCache {
+    years: HashMap<i32, [(NaiveDateTime, NaiveDateTime); 4]>,
+}
+
+impl QuarterBoundsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the start and end of the quarter containing `date`, matching
+    /// `start_of_quarter` and `end_of_quarter` from `generate_coordinates`.
+    pub fn quarter_bounds_cached(
+        &mut self,
+        date: &DateTime<FixedOffset>,
+    ) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
+        let (start, end) = self
+            .years
+            .entry(date.year())
+            .or_insert_with(|| quarter_boundaries(date.year()))[quarter_of(date) as usize - 1];
+        (
+            date.offset().from_local_datetime(&start).unwrap(),
+            date.offset().from_local_datetime(&end).unwrap(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_coordinates;
+    use chrono::Days;
+
+    #[test]
+    fn test_lean_path_agrees_with_generate_coordinates() {
+        let mut cache = QuarterBoundsCache::new();
+        let mut date = DateTime::parse_from_rfc3339("2000-01-01T16:39:57+05:00").unwrap();
+        while date.year() == 2000 {
+            let coordinates = generate_coordinates(&date);
+            assert_eq!(coordinates.quarter, quarter_of(&date));
+            assert_eq!(
+                (coordinates.start_of_quarter, coordinates.end_of_quarter),
+                cache.quarter_bounds_cached(&date)
+            );
+            date = date.checked_add_days(Days::new(1)).unwrap();
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use crate::CorporateCoordinates;
+
+/// What a previous run recorded, so the next run can compare against it.
+#[derive(Debug, PartialEq)]
+pub struct Snapshot {
+    /// Names the calendar the run used, e.g. `fiscal-4-end-year`. Quarters
+    /// and percentages are only comparable within one calendar.
+    pub calendar: String,
+    pub year_quarter_id: String,
+    pub percent_remaining: f64,
+}
+
+impl Snapshot {
+    pub fn of(coordinates: &CorporateCoordinates, calendar: &str) -> Snapshot {
+        Snapshot {
+            calendar: calendar.to_string(),
+            year_quarter_id: coordinates.year_quarter_id(),
+            percent_remaining: coordinates.percent_remaining,
+        }
+    }
+
+    /// Parses the `key=value` lines written by `to_cache_string`.
+    pub fn parse(s: &str) -> Option<Snapshot> {
+        let value = |key: &str| {
+            s.lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(k, _)| k.trim() == key)
+                .map(|(_, v)| v.trim())
+        };
+        Some(Snapshot {
+            calendar: value("calendar")?.to_string(),
+            year_quarter_id: value("quarter")?.to_string(),
+            percent_remaining: value("percent_remaining")?.parse().ok()?,
+        })
+    }
+
+    pub fn to_cache_string(&self) -> String {
+        format!(
+            "calendar={}\nquarter={}\npercent_remaining={}\n",
+            self.calendar, self.year_quarter_id, self.percent_remaining
+        )
+    }
+
+    /// Loads the snapshot at `path`, if there is a readable one from a run
+    /// in `calendar`.
+    pub fn load(path: &Path, calendar: &str) -> Option<Snapshot> {
+        Snapshot::parse(&fs::read_to_string(path).ok()?)
+            .filter(|snapshot| snapshot.calendar == calendar)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_cache_string())
+    }
+}
+
+/// Where the last run's snapshot is kept: under `$XDG_CACHE_HOME`, or
+/// `~/.cache` if that isn't set.
+pub fn default_snapshot_path() -> Option<PathBuf> {
+    let cache_dir = env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(cache_dir.join("corporateclock").join("last_run"))
+}
+
+/// A `QUARTER ROLLED OVER: Q1 → Q2` line if `current` is in a different
+/// quarter to the `previous` run. The years are shown too if they differ.
+pub fn rollover_message(previous: &Snapshot, current: &CorporateCoordinates) -> Option<String> {
+    let current_id = current.year_quarter_id();
+    if previous.year_quarter_id == current_id {
+        return None;
+    }
+    let same_year = previous.year_quarter_id.split('Q').next() == Some(current.year.as_str());
+    let quarter = |id: &str| id.find('Q').map_or(id.to_string(), |i| id[i..].to_string());
+    let (from, to) = if same_year {
+        (quarter(&previous.year_quarter_id), quarter(&current_id))
+    } else {
+        (previous.year_quarter_id.clone(), current_id)
+    };
+    Some(format!("QUARTER ROLLED OVER: {} → {}", from, to))
+}
+
+/// An arrow showing which way a percentage moved since the previous run, or
+/// `None` if there was no previous run or it hasn't moved.
+pub fn trend_arrow(previous: Option<f64>, current: f64) -> Option<char> {
+    match previous?.partial_cmp(&current)? {
+        std::cmp::Ordering::Less => Some('▲'),
+        std::cmp::Ordering::Greater => Some('▼'),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_coordinates;
+    use chrono::DateTime;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let snapshot = Snapshot {
+            calendar: "fiscal-1-end-year".to_string(),
+            year_quarter_id: "1999Q2".to_string(),
+            percent_remaining: 47.25,
+        };
+        assert_eq!(
+            Some(&snapshot),
+            Snapshot::parse(&snapshot.to_cache_string()).as_ref()
+        );
+        assert_eq!(
+            Some(snapshot),
+            Snapshot::parse(
+                "calendar=fiscal-1-end-year\nquarter=1999Q2\npercent_remaining=47.25\n"
+            )
+        );
+        assert_eq!(
+            None,
+            Snapshot::parse("calendar=fiscal-1-end-year\nquarter=1999Q2\n")
+        );
+        // Snapshots from before the calendar was recorded can't be compared.
+        assert_eq!(
+            None,
+            Snapshot::parse("quarter=1999Q2\npercent_remaining=47.25\n")
+        );
+    }
+
+    #[test]
+    fn test_snapshot_from_another_calendar_is_ignored() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let path = std::env::temp_dir()
+            .join(format!("corporateclock-cache-test-{}", std::process::id()))
+            .join("last_run");
+        Snapshot::of(&generate_coordinates(&t), "fiscal-1-end-year")
+            .save(&path)
+            .unwrap();
+        let calendar_year = Snapshot::load(&path, "fiscal-1-end-year");
+        let fiscal_year = Snapshot::load(&path, "fiscal-10-end-year");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(
+            Some("1999Q2"),
+            calendar_year.as_ref().map(|s| s.year_quarter_id.as_str())
+        );
+        assert_eq!(None, fiscal_year);
+    }
+
+    #[test]
+    fn test_trend_arrow_from_stored_value() {
+        let previous =
+            Snapshot::parse("calendar=months\nquarter=1999Q2\npercent_remaining=50\n").unwrap();
+        assert_eq!(
+            Some('▼'),
+            trend_arrow(Some(previous.percent_remaining), 48.9)
+        );
+        assert_eq!(
+            Some('▲'),
+            trend_arrow(Some(previous.percent_remaining), 98.9)
+        );
+        assert_eq!(None, trend_arrow(Some(previous.percent_remaining), 50.0));
+        assert_eq!(None, trend_arrow(None, 48.9));
+    }
+
+    #[test]
+    fn test_rollover_message() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let current = generate_coordinates(&t);
+        let cached = |id: &str| {
+            Snapshot::parse(&format!(
+                "calendar=months\nquarter={}\npercent_remaining=1\n",
+                id
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(
+            Some("QUARTER ROLLED OVER: Q1 → Q2".to_string()),
+            rollover_message(&cached("1999Q1"), &current)
+        );
+        assert_eq!(
+            Some("QUARTER ROLLED OVER: 1998Q4 → 1999Q2".to_string()),
+            rollover_message(&cached("1998Q4"), &current)
+        );
+        assert_eq!(None, rollover_message(&cached("1999Q2"), &current));
+    }
+}
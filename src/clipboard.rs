@@ -0,0 +1,5 @@
+/// Copies `text` to the system clipboard.
+/// Fails if there is no clipboard, e.g. in a headless session.
+pub fn copy_text(text: &str) -> Result<(), arboard::Error> {
+    arboard::Clipboard::new()?.set_text(text)
+}
@@ -0,0 +1,197 @@
+use chrono::prelude::*;
+use std::str::FromStr;
+
+use crate::{generate_coordinates_for_quarter, CoordinateError, CorporateCoordinates};
+
+/// Parses a quarter written like `Q2-2024` into its year and quarter.
+pub fn parse_quarter_spec(spec: &str) -> Result<(i32, u32), CoordinateError> {
+    let invalid = || CoordinateError::InvalidQuarter(spec.to_string());
+    let (quarter, year) = spec
+        .trim()
+        .strip_prefix(['Q', 'q'])
+        .and_then(|rest| rest.split_once('-'))
+        .ok_or_else(invalid)?;
+    let quarter: u32 = quarter.parse().map_err(|_| invalid())?;
+    let year: i32 = year.parse().map_err(|_| invalid())?;
+    if !(1..=4).contains(&quarter) {
+        return Err(invalid());
+    }
+    Ok((year, quarter))
+}
+
+/// A quarter of a (fiscal) year, written like `Q1-2022`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QuarterId {
+    pub year: i32,
+    pub quarter: u32,
+}
+
+impl QuarterId {
+    /// Quarters since the year 0, for counting between quarters.
+    fn index(&self) -> i32 {
+        self.year * 4 + self.quarter as i32 - 1
+    }
+}
+
+impl FromStr for QuarterId {
+    type Err = CoordinateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, quarter) = parse_quarter_spec(s)?;
+        Ok(QuarterId { year, quarter })
+    }
+}
+
+impl CorporateCoordinates {
+    /// The quarter these coordinates are in, by fiscal year. Coordinates
+    /// under one fiscal calendar can be grouped by quarter with this as the
+    /// key.
+    #[must_use]
+    pub fn quarter_id(&self) -> QuarterId {
+        QuarterId {
+            year: self.year.parse().expect("years are always numeric"),
+            quarter: self.quarter,
+        }
+    }
+
+    /// How many quarters have passed since `epoch`, e.g. 1 in the quarter
+    /// after it, and negative if `epoch` is still to come.
+    #[must_use]
+    pub fn fiscal_quarters_since_epoch(&self, epoch: QuarterId) -> i32 {
+        self.quarter_id().index() - epoch.index()
+    }
+}
+
+/// How a quarter differs from another, as `to` minus `from`.
+pub struct QuarterDiff {
+    pub from: CorporateCoordinates,
+    pub to: CorporateCoordinates,
+    pub days: i64,
+    pub business_days: i64,
+    pub start_weekday_days: i64,
+}
+
+/// Splits a signed difference into its size and the word for its direction,
+/// the first of `words` if it isn't negative, e.g. `(1, "shorter")` for -1
+/// with `("longer", "shorter")`.
+pub fn difference_in_words(
+    difference: i64,
+    words: (&'static str, &'static str),
+) -> (u64, &'static str) {
+    let word = if difference < 0 { words.1 } else { words.0 };
+    (difference.unsigned_abs(), word)
+}
+
+pub fn quarter_diff(from: CorporateCoordinates, to: CorporateCoordinates) -> QuarterDiff {
+    let weekday = |c: &CorporateCoordinates| c.start_of_quarter.weekday().num_days_from_monday();
+    QuarterDiff {
+        days: to.days_in_quarter as i64 - from.days_in_quarter as i64,
+        business_days: to.total_business_days_in_quarter as i64
+            - from.total_business_days_in_quarter as i64,
+        start_weekday_days: weekday(&to) as i64 - weekday(&from) as i64,
+        from,
+        to,
+    }
+}
+
+/// Compares two quarters given as specs like `Q2-2024`.
+pub fn diff_quarter_specs(
+    from: &str,
+    to: &str,
+    offset: FixedOffset,
+) -> Result<QuarterDiff, CoordinateError> {
+    let (from_year, from_quarter) = parse_quarter_spec(from)?;
+    let (to_year, to_quarter) = parse_quarter_spec(to)?;
+    Ok(quarter_diff(
+        generate_coordinates_for_quarter(from_year, from_quarter, offset)?,
+        generate_coordinates_for_quarter(to_year, to_quarter, offset)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc() -> FixedOffset {
+        FixedOffset::east_opt(0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_quarter_spec() {
+        assert_eq!((2024, 2), parse_quarter_spec("Q2-2024").unwrap());
+        assert_eq!((1999, 4), parse_quarter_spec("q4-1999").unwrap());
+        assert!(parse_quarter_spec("Q5-2024").is_err());
+        assert!(parse_quarter_spec("2024-Q2").is_err());
+        assert!(parse_quarter_spec("Q2").is_err());
+    }
+
+    #[test]
+    fn test_fiscal_quarters_since_epoch() {
+        let t = DateTime::parse_from_rfc3339("2025-08-16T16:39:57+00:00").unwrap();
+        let coordinates = crate::generate_coordinates(&t);
+        let since = |epoch: &str| coordinates.fiscal_quarters_since_epoch(epoch.parse().unwrap());
+        assert_eq!(14, since("Q1-2022"));
+        assert_eq!(0, since("Q3-2025"));
+        assert_eq!(-2, since("Q1-2026"));
+
+        // Fiscal years starting in October put August in Q4 of FY2025.
+        let fiscal = crate::generate_fiscal_coordinates(&t, 10);
+        assert_eq!(
+            3,
+            fiscal.fiscal_quarters_since_epoch("Q1-2025".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_quarter_id_groups_by_quarter() {
+        let quarters: std::collections::HashSet<QuarterId> = [
+            "1999-04-01T16:39:57+00:00",
+            "1999-06-30T09:00:00+05:00",
+            "1999-07-01T16:39:57+00:00",
+        ]
+        .iter()
+        .map(|t| {
+            crate::generate_coordinates(&DateTime::parse_from_rfc3339(t).unwrap()).quarter_id()
+        })
+        .collect();
+        assert_eq!(2, quarters.len());
+        assert!(quarters.contains(&"Q2-1999".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_quarter_diff() {
+        let diff = diff_quarter_specs("Q2-2024", "Q2-2025", utc()).unwrap();
+        // Q2 2024 starts on a Monday and Q2 2025 on a Tuesday.
+        assert_eq!(0, diff.days);
+        assert_eq!(0, diff.business_days);
+        assert_eq!(1, diff.start_weekday_days);
+
+        // Q1 2024 is a leap year quarter of 91 days, Q1 2025 has 90.
+        let diff = diff_quarter_specs("Q1-2024", "Q1-2025", utc()).unwrap();
+        assert_eq!(-1, diff.days);
+        assert_eq!(-1, diff.business_days);
+    }
+
+    #[test]
+    fn test_negative_diff_in_words() {
+        let diff = diff_quarter_specs("Q1-2024", "Q1-2025", utc()).unwrap();
+        assert_eq!(
+            (1, "shorter"),
+            difference_in_words(diff.days, ("longer", "shorter"))
+        );
+        assert_eq!(
+            (1, "fewer"),
+            difference_in_words(diff.business_days, ("more", "fewer"))
+        );
+
+        let diff = diff_quarter_specs("Q2-2025", "Q2-2024", utc()).unwrap();
+        assert_eq!(
+            (1, "earlier"),
+            difference_in_words(diff.start_weekday_days, ("later", "earlier"))
+        );
+        assert_eq!(
+            (0, "longer"),
+            difference_in_words(diff.days, ("longer", "shorter"))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::prelude::*;
+use chrono::Days;
+use clap::ValueEnum;
+
+use crate::CorporateCoordinates;
+
+/// How days are counted within a quarter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum DayCount {
+    /// Actual calendar days
+    #[default]
+    Actual,
+    /// 30/360: every month has 30 days, so every quarter has 90
+    #[value(name = "30-360")]
+    Thirty360,
+}
+
+/// Days from `start` to `end` under the 30/360 (US bond basis) convention.
+pub fn days_30_360(start: NaiveDate, end: NaiveDate) -> i64 {
+    let start_day = start.day().min(30) as i64;
+    let end_day = if end.day() == 31 && start_day == 30 {
+        30
+    } else {
+        end.day() as i64
+    };
+    360 * (end.year() - start.year()) as i64
+        + 30 * (end.month() as i64 - start.month() as i64)
+        + (end_day - start_day)
+}
+
+impl CorporateCoordinates {
+    /// The same coordinates with the day counts and percentages recomputed
+    /// under `day_count`, over the period they measure: 90 days for a
+    /// quarter, 30 for a month, or up to a deadline.
+    #[must_use]
+    pub fn with_day_count(mut self, day_count: DayCount) -> CorporateCoordinates {
+        if day_count == DayCount::Actual {
+            return self;
+        }
+        let start = self.start_of_quarter.date_naive();
+        let last_day = self.end_of_quarter.date_naive();
+        let days = days_30_360(start, last_day + Days::new(1)).max(1);
+        // The 31st of a period's last month counts as one day past its end,
+        // so it shares the last day with the 30th.
+        let elapsed = days_30_360(start, self.generation_time.date_naive()).clamp(0, days - 1);
+        let (days, elapsed) = (days as u32, elapsed as u32);
+        self.days_in_quarter = days;
+        self.day_of_quarter = elapsed + 1;
+        self.days_left_in_quarter = days - elapsed;
+        self.days_remaining_after_today = days - elapsed - 1;
+        self.percent_remaining = self.days_left_in_quarter as f64 / days as f64 * 100.0;
+        self.percent_elapsed = 100.0 - self.percent_remaining;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_coordinates;
+
+    #[test]
+    fn test_days_30_360_across_a_31_day_month() {
+        let start = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        assert_eq!(45, end.signed_duration_since(start).num_days());
+        assert_eq!(44, days_30_360(start, end));
+
+        let last = NaiveDate::from_ymd_opt(2025, 7, 31).unwrap();
+        let next_last = NaiveDate::from_ymd_opt(2025, 8, 31).unwrap();
+        assert_eq!(30, days_30_360(last, next_last));
+    }
+
+    #[test]
+    fn test_with_day_count() {
+        let t = DateTime::parse_from_rfc3339("2025-08-15T12:00:00+00:00").unwrap();
+        let coordinates = generate_coordinates(&t).with_day_count(DayCount::Thirty360);
+        assert_eq!(90, coordinates.days_in_quarter);
+        assert_eq!(45, coordinates.day_of_quarter);
+        assert_eq!(46, coordinates.days_left_in_quarter);
+    }
+
+    #[test]
+    fn test_with_day_count_on_last_day_of_quarter() {
+        for date in ["2025-03-30T12:00:00+00:00", "2025-03-31T12:00:00+00:00"] {
+            let t = DateTime::parse_from_rfc3339(date).unwrap();
+            let coordinates = generate_coordinates(&t).with_day_count(DayCount::Thirty360);
+            assert_eq!(90, coordinates.day_of_quarter);
+            assert_eq!(1, coordinates.days_left_in_quarter);
+            assert_eq!(0, coordinates.days_remaining_after_today);
+        }
+    }
+
+    #[test]
+    fn test_with_day_count_over_a_month_and_to_a_deadline() {
+        let t = DateTime::parse_from_rfc3339("2025-08-15T12:00:00+00:00").unwrap();
+        let month = crate::generate_month_coordinates(&t).with_day_count(DayCount::Thirty360);
+        assert_eq!(30, month.days_in_quarter);
+        assert_eq!(15, month.day_of_quarter);
+        assert_eq!(16, month.days_left_in_quarter);
+        assert_eq!(16.0 / 30.0 * 100.0, month.percent_remaining);
+
+        let deadline = NaiveDate::from_ymd_opt(2025, 8, 31).unwrap();
+        let to_deadline = generate_coordinates(&t)
+            .with_deadline(deadline)
+            .unwrap()
+            .with_day_count(DayCount::Thirty360);
+        assert_eq!(60, to_deadline.days_in_quarter);
+        assert_eq!(16, to_deadline.days_left_in_quarter);
+    }
+}
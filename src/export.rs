@@ -0,0 +1,686 @@
+use chrono::{Datelike, Weekday};
+use clap::ValueEnum;
+
+use crate::render::UrgencyThresholds;
+use crate::{CoordinateError, CorporateCoordinates};
+
+/// The overall shape of the program's output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// The colored prose summary
+    #[default]
+    Text,
+    /// A JSON desktop notification payload with a title and body
+    Notify,
+    /// `CC_`-prefixed `NAME=value` lines for shell environments
+    Env,
+    /// An Emacs Org-mode headline scheduled over the quarter
+    Org,
+    /// The coordinates as a JSON object
+    #[cfg(feature = "json")]
+    Json,
+    /// The coordinates as compact JSON, base64-encoded on one line
+    #[cfg(feature = "json")]
+    JsonBase64,
+}
+
+/// Which percentage remaining drives a badge's urgency color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum BadgeBasis {
+    #[default]
+    Calendar,
+    Business,
+}
+
+/// Which percentage of the quarter single-number output reports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum PercentMode {
+    #[default]
+    Remaining,
+    Elapsed,
+}
+
+impl CorporateCoordinates {
+    /// Formats the coordinates as Graphite plaintext protocol lines
+    /// (`<metric_path> <value> <unix_timestamp>`) under `prefix`.
+    #[must_use]
+    pub fn to_graphite_metrics(&self, prefix: &str) -> String {
+        let timestamp = self.generation_time.timestamp();
+        [
+            ("quarter", self.quarter),
+            ("full_weeks_done", self.full_week_of_quarter_done),
+            ("weeks_in_quarter", self.weeks_in_quarter),
+            ("days_left", self.days_left_in_quarter),
+            ("days_in_quarter", self.days_in_quarter),
+        ]
+        .iter()
+        .map(|(name, value)| format!("{}.{} {} {}\n", prefix, name, value, timestamp))
+        .collect()
+    }
+
+    /// Returns a shields.io static badge URL showing the percentage of the
+    /// quarter remaining, colored by urgency on the chosen `basis`.
+    #[must_use]
+    pub fn to_shields_badge_url(
+        &self,
+        basis: BadgeBasis,
+        thresholds: &UrgencyThresholds,
+    ) -> String {
+        let percent_remaining = match basis {
+            BadgeBasis::Calendar => self.percent_remaining,
+            BadgeBasis::Business => self.business_percent_remaining,
+        };
+        let escape = |s: String| {
+            s.replace('-', "--")
+                .replace('_', "__")
+                .replace('%', "%25")
+                .replace(' ', "%20")
+        };
+        format!(
+            "https://img.shields.io/badge/{}-{}-{}",
+            escape(format!("Q{} {}", self.quarter, self.year)),
+            escape(format!("{:.2}% remaining", percent_remaining)),
+            thresholds.color(percent_remaining)
+        )
+    }
+
+    /// Serializes the coordinates as pretty-printed JSON, using the same field
+    /// names as the struct, plus the `risk_level`.
+    #[cfg(feature = "json")]
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&WithRiskLevel::of(self))
+            .expect("coordinates always serialize")
+    }
+
+    /// Serializes the coordinates as compact JSON and base64-encodes it, for
+    /// systems that mangle quotes or newlines.
+    #[cfg(feature = "json")]
+    #[must_use]
+    pub fn to_json_base64(&self) -> String {
+        use base64::Engine;
+
+        let json =
+            serde_json::to_string(&WithRiskLevel::of(self)).expect("coordinates always serialize");
+        base64::engine::general_purpose::STANDARD.encode(json)
+    }
+
+    /// Serializes the coordinates as YAML, with the same fields as `to_json`.
+    #[cfg(feature = "yaml")]
+    #[must_use]
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(&WithRiskLevel::of(self)).expect("coordinates always serialize")
+    }
+
+    /// Serializes the coordinates as pretty-printed RON, which can be read
+    /// back with `from_ron`. Unlike JSON and YAML it has no `risk_level`, as
+    /// that can't be read back.
+    #[cfg(feature = "ron")]
+    #[must_use]
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .expect("coordinates always serialize")
+    }
+
+    /// Deserializes coordinates written by `to_ron`.
+    #[cfg(feature = "ron")]
+    pub fn from_ron(s: &str) -> Result<CorporateCoordinates, ron::error::SpannedError> {
+        ron::from_str(s)
+    }
+
+    /// The coordinates as `CC_`-prefixed environment variables.
+    #[must_use]
+    pub fn to_env_vars(&self) -> Vec<(String, String)> {
+        [
+            ("CC_QUARTER", self.quarter.to_string()),
+            ("CC_YEAR", self.year.clone()),
+            ("CC_QUARTER_ID", self.year_quarter_id()),
+            ("CC_START_OF_QUARTER", self.start_of_quarter.to_rfc3339()),
+            ("CC_END_OF_QUARTER", self.end_of_quarter.to_rfc3339()),
+            ("CC_WEEKS_DONE", self.full_week_of_quarter_done.to_string()),
+            ("CC_DAYS_LEFT", self.days_left_in_quarter.to_string()),
+            ("CC_DAYS_IN_QUARTER", self.days_in_quarter.to_string()),
+            (
+                "CC_PERCENT_REMAINING",
+                format!("{:.2}", self.percent_remaining),
+            ),
+            ("CC_PERCENT_ELAPSED", format!("{:.2}", self.percent_elapsed)),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+    }
+
+    /// A JSON payload with a `title` and `body` for desktop notifiers.
+    #[must_use]
+    pub fn to_notify_payload(&self) -> String {
+        format!(
+            "{{\"title\":{},\"body\":{}}}",
+            json_string(&format!("Q{} {}", self.quarter, self.year)),
+            json_string(&format!(
+                "{:.0}% of the quarter remains ({} days)",
+                self.percent_remaining, self.days_left_in_quarter
+            ))
+        )
+    }
+
+    /// A Slack attachment colored by risk level, holding Block Kit blocks: a
+    /// header naming the quarter and a section with the time remaining.
+    #[must_use]
+    pub fn to_slack_attachment(&self) -> String {
+        format!(
+            concat!(
+                "{{\"attachments\":[{{\"color\":{},\"blocks\":[",
+                "{{\"type\":\"header\",\"text\":{{\"type\":\"plain_text\",\"text\":{}}}}},",
+                "{{\"type\":\"section\",\"text\":{{\"type\":\"mrkdwn\",\"text\":{}}}}}",
+                "]}}]}}"
+            ),
+            json_string(self.risk_level().slack_color()),
+            json_string(&self.quarter_label()),
+            json_string(&format!(
+                "*{:.2}%* of the quarter remains: {} days, ending {}.",
+                self.percent_remaining,
+                self.days_left_in_quarter,
+                self.end_of_quarter.format("%A, %d %B")
+            ))
+        )
+    }
+
+    /// A `chat.postMessage` request body posting `to_slack_attachment` to
+    /// `channel`.
+    #[must_use]
+    pub fn to_slack_message(&self, channel: &str) -> String {
+        format!(
+            "{{\"channel\":{},{}",
+            json_string(channel),
+            &self.to_slack_attachment()[1..]
+        )
+    }
+
+    /// An Org-mode headline for the quarter, scheduled from its start with a
+    /// deadline at its end, and a property drawer.
+    #[must_use]
+    pub fn to_org(&self) -> String {
+        format!(
+            concat!(
+                "* {}\n",
+                "SCHEDULED: <{}> DEADLINE: <{}>\n",
+                ":PROPERTIES:\n",
+                ":QUARTER: {}\n",
+                ":DAYS_LEFT: {}\n",
+                ":PERCENT_REMAINING: {:.2}\n",
+                ":END:\n"
+            ),
+            self.quarter_label(),
+            self.start_of_quarter.format("%Y-%m-%d %a"),
+            self.end_of_quarter.format("%Y-%m-%d %a"),
+            self.year_quarter_id(),
+            self.days_left_in_quarter,
+            self.percent_remaining
+        )
+    }
+
+    /// An iCalendar RRULE recurring on the same weekday of the same month of
+    /// every quarter as the generation time, e.g. the third Sunday of the
+    /// second month: `RRULE:FREQ=YEARLY;BYMONTH=2,5,8,11;BYDAY=3SU`. A fifth
+    /// weekday becomes the last one (`-1`), since not every month has five.
+    #[must_use]
+    pub fn to_rrule(&self) -> String {
+        let date = self.generation_time.date_naive();
+        let mut months: Vec<u32> = (0..4).map(|i| (date.month0() + i * 3) % 12 + 1).collect();
+        months.sort_unstable();
+        let nth = match date.day0() / 7 + 1 {
+            5 => -1,
+            nth => nth as i32,
+        };
+        let weekday = match date.weekday() {
+            Weekday::Mon => "MO",
+            Weekday::Tue => "TU",
+            Weekday::Wed => "WE",
+            Weekday::Thu => "TH",
+            Weekday::Fri => "FR",
+            Weekday::Sat => "SA",
+            Weekday::Sun => "SU",
+        };
+        format!(
+            "RRULE:FREQ=YEARLY;BYMONTH={};BYDAY={}{}",
+            months
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            nth,
+            weekday
+        )
+    }
+
+    /// A minimal Outlook task, due at the end of the quarter, for importing
+    /// the quarter-end deadline into Outlook.
+    #[must_use]
+    pub fn to_outlook_task_xml(&self) -> String {
+        format!(
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<Task>\n",
+                "  <Subject>End of Q{} FY{}</Subject>\n",
+                "  <StartDate>{}</StartDate>\n",
+                "  <DueDate>{}</DueDate>\n",
+                "  <Status>NotStarted</Status>\n",
+                "</Task>\n"
+            ),
+            self.quarter,
+            self.year,
+            self.start_of_quarter.format("%Y-%m-%d"),
+            self.end_of_quarter.format("%Y-%m-%d")
+        )
+    }
+
+    /// A vCard `NOTE` value summarizing the quarter, escaped as RFC 2426
+    /// requires.
+    #[must_use]
+    pub fn to_vcard_note(&self) -> String {
+        let note = format!(
+            "{}: {:.2}% of the quarter remains, {} days, from {} to {}.\nGenerated {}.",
+            self.quarter_label(),
+            self.percent_remaining,
+            self.days_left_in_quarter,
+            self.start_of_quarter.format("%Y-%m-%d"),
+            self.end_of_quarter.format("%Y-%m-%d"),
+            self.generation_time.format("%+")
+        );
+        vcard_escape(&note)
+    }
+
+    /// Formats the fraction (0-1) of the quarter elapsed to four significant
+    /// figures, e.g. `0.4835` or `0.05123`, for spreadsheet formulas.
+    #[must_use]
+    pub fn quarter_fraction_elapsed_as_decimal(&self) -> String {
+        let fraction = self.percent_elapsed / 100.0;
+        let decimals = |x: f64| {
+            if x == 0.0 {
+                4
+            } else {
+                (3 - x.abs().log10().floor() as i32).max(0) as usize
+            }
+        };
+        // Rounding can carry into the next power of ten, e.g. 0.099996 to
+        // 0.1000, which needs one decimal place fewer.
+        let rounded: f64 = format!("{:.*}", decimals(fraction), fraction)
+            .parse()
+            .unwrap();
+        format!("{:.*}", decimals(rounded), rounded)
+    }
+
+    /// The percentage of the quarter remaining or elapsed, rounded to a whole
+    /// number with no other text, e.g. `47`.
+    #[must_use]
+    pub fn to_percent_only(&self, mode: PercentMode) -> String {
+        let percent = match mode {
+            PercentMode::Remaining => self.percent_remaining,
+            PercentMode::Elapsed => self.percent_elapsed,
+        };
+        format!("{:.0}", percent)
+    }
+
+    /// A sortable, filename-safe summary like `2024-Q2_week07_47pct`, with the
+    /// current week of the quarter and the percentage elapsed.
+    #[must_use]
+    pub fn to_filename_stub(&self) -> String {
+        format!(
+            "{}_week{:02}_{:.0}pct",
+            self.quarter_iso_label(),
+            self.full_week_of_quarter_done + 1,
+            self.percent_elapsed
+        )
+    }
+
+    /// A single `key=value` line prefixed with the generation time, for
+    /// appending to an audit log.
+    #[must_use]
+    pub fn audit_log_line(&self) -> String {
+        format!(
+            "[{}] quarter=Q{} year={} week={} days_left={} pct_elapsed={:.2}",
+            self.generation_time.to_rfc3339(),
+            self.quarter,
+            self.year,
+            self.full_week_of_quarter_done + 1,
+            self.days_left_in_quarter,
+            self.percent_elapsed
+        )
+    }
+
+    /// Formats the coordinates as a single InfluxDB line protocol point with
+    /// a nanosecond timestamp. Fails for times too far from 1970 for that,
+    /// outside roughly 1677 to 2262.
+    pub fn to_influx_line_protocol(&self) -> Result<String, CoordinateError> {
+        let nanos = self.generation_time.timestamp_nanos_opt().ok_or(
+            CoordinateError::TimestampOutOfRange(self.generation_time.timestamp()),
+        )?;
+        Ok(format!(
+            "corporate_clock,quarter=Q{},year={} days_left={},percent_elapsed={:.2} {}",
+            self.quarter, self.year, self.days_left_in_quarter, self.percent_elapsed, nanos
+        ))
+    }
+}
+
+/// Serializes several sets of coordinates as a pretty-printed JSON array.
+#[cfg(feature = "json")]
+pub fn to_json_array(coordinates: &[CorporateCoordinates]) -> String {
+    let values: Vec<WithRiskLevel> = coordinates.iter().map(WithRiskLevel::of).collect();
+    serde_json::to_string_pretty(&values).expect("coordinates always serialize")
+}
+
+/// The coordinates' fields followed by their `risk_level`, for JSON and YAML
+/// output.
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[derive(serde::Serialize)]
+struct WithRiskLevel<'a> {
+    #[serde(flatten)]
+    coordinates: &'a CorporateCoordinates,
+    risk_level: String,
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+impl<'a> WithRiskLevel<'a> {
+    fn of(coordinates: &'a CorporateCoordinates) -> Self {
+        WithRiskLevel {
+            coordinates,
+            risk_level: coordinates.risk_level().to_string(),
+        }
+    }
+}
+
+/// Escapes backslashes, commas, semicolons and newlines in a vCard value.
+fn vcard_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' | ',' | ';' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_coordinates;
+    use chrono::DateTime;
+
+    #[test]
+    fn test_graphite_metrics() {
+        let t = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
+        let metrics = generate_coordinates(&t).to_graphite_metrics("company.quarter");
+        let lines: Vec<&str> = metrics.lines().collect();
+
+        assert_eq!(5, lines.len());
+        assert!(lines.contains(&"company.quarter.quarter 2 922984797"));
+        assert!(lines.contains(&"company.quarter.days_left 90 922984797"));
+        assert!(lines.contains(&"company.quarter.days_in_quarter 90 922984797"));
+    }
+
+    #[test]
+    fn test_quarter_fraction_elapsed_as_decimal() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "0.5000",
+            generate_coordinates(&t).quarter_fraction_elapsed_as_decimal()
+        );
+
+        let t = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "0.0000",
+            generate_coordinates(&t).quarter_fraction_elapsed_as_decimal()
+        );
+
+        // On day 5, 4 of the 90 days (4.444%) have passed.
+        let t = DateTime::parse_from_rfc3339("1999-04-05T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "0.04444",
+            generate_coordinates(&t).quarter_fraction_elapsed_as_decimal()
+        );
+
+        let t = DateTime::parse_from_rfc3339("1999-06-30T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "0.9889",
+            generate_coordinates(&t).quarter_fraction_elapsed_as_decimal()
+        );
+    }
+
+    #[test]
+    fn test_env_vars() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let vars = generate_coordinates(&t).to_env_vars();
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(Some("2"), var("CC_QUARTER"));
+        assert_eq!(Some("1999Q2"), var("CC_QUARTER_ID"));
+        assert_eq!(Some("45"), var("CC_DAYS_LEFT"));
+        assert_eq!(Some("50.00"), var("CC_PERCENT_REMAINING"));
+    }
+
+    #[test]
+    fn test_notify_payload() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(
+            r#"{"title":"Q2 1999","body":"50% of the quarter remains (45 days)"}"#,
+            generate_coordinates(&t).to_notify_payload()
+        );
+    }
+
+    #[test]
+    fn test_slack_message() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(
+            concat!(
+                r#"{"channel":"general","attachments":[{"color":"good","blocks":["#,
+                r#"{"type":"header","text":{"type":"plain_text","text":"Q2, 1999"}},"#,
+                r#"{"type":"section","text":{"type":"mrkdwn","#,
+                r#""text":"*50.00%* of the quarter remains: 45 days, ending Wednesday, 30 June."}}"#,
+                "]}]}"
+            ),
+            generate_coordinates(&t).to_slack_message("general")
+        );
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(r#""say \"hi\"\n""#, json_string("say \"hi\"\n"));
+    }
+
+    #[test]
+    fn test_org() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let org = generate_coordinates(&t).to_org();
+        assert!(org.starts_with("* Q2, 1999\n"));
+        assert!(org.contains("DEADLINE: <1999-06-30 Wed>"));
+        assert!(org.contains(":DAYS_LEFT: 45\n"));
+        assert!(org.ends_with(":END:\n"));
+    }
+
+    #[test]
+    fn test_rrule() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "RRULE:FREQ=YEARLY;BYMONTH=2,5,8,11;BYDAY=3SU",
+            generate_coordinates(&t).to_rrule()
+        );
+        let t = DateTime::parse_from_rfc3339("1999-01-29T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "RRULE:FREQ=YEARLY;BYMONTH=1,4,7,10;BYDAY=-1FR",
+            generate_coordinates(&t).to_rrule()
+        );
+    }
+
+    #[test]
+    fn test_outlook_task_xml() {
+        let t = DateTime::parse_from_rfc3339("2025-05-16T16:39:57+00:00").unwrap();
+        let xml = generate_coordinates(&t).to_outlook_task_xml();
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<Subject>End of Q2 FY2025</Subject>"));
+        assert!(xml.contains("<DueDate>2025-06-30</DueDate>"));
+    }
+
+    #[test]
+    fn test_vcard_note() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(
+            concat!(
+                r"Q2\, 1999: 50.00% of the quarter remains\, 45 days\, ",
+                r"from 1999-04-01 to 1999-06-30.\nGenerated 1999-05-16T16:39:57+00:00."
+            ),
+            generate_coordinates(&t).to_vcard_note()
+        );
+        assert_eq!(r"a\;b\\c", vcard_escape("a;b\\c"));
+    }
+
+    #[test]
+    fn test_percent_only() {
+        let t = DateTime::parse_from_rfc3339("1999-06-01T16:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        assert_eq!("32", coordinates.to_percent_only(PercentMode::Remaining));
+        assert_eq!("68", coordinates.to_percent_only(PercentMode::Elapsed));
+    }
+
+    #[test]
+    fn test_filename_stub() {
+        let t = DateTime::parse_from_rfc3339("2024-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "2024-Q2_week07_50pct",
+            generate_coordinates(&t).to_filename_stub()
+        );
+    }
+
+    #[test]
+    fn test_audit_log_line() {
+        let t = DateTime::parse_from_rfc3339("2025-04-15T10:00:00+00:00").unwrap();
+        assert_eq!(
+            "[2025-04-15T10:00:00+00:00] quarter=Q2 year=2025 week=3 days_left=76 pct_elapsed=15.56",
+            generate_coordinates(&t).audit_log_line()
+        );
+    }
+
+    #[test]
+    fn test_influx_line_protocol() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "corporate_clock,quarter=Q2,year=1999 days_left=45,percent_elapsed=50.00 926872797000000000",
+            generate_coordinates(&t).to_influx_line_protocol().unwrap()
+        );
+
+        let far_future = DateTime::parse_from_rfc3339("2300-01-01T00:00:00+00:00").unwrap();
+        assert_eq!(
+            Err(CoordinateError::TimestampOutOfRange(10413792000)),
+            generate_coordinates(&far_future).to_influx_line_protocol()
+        );
+    }
+
+    #[test]
+    fn test_shields_badge_url() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "https://img.shields.io/badge/Q2%201999-50.00%25%20remaining-yellow",
+            generate_coordinates(&t)
+                .to_shields_badge_url(BadgeBasis::Calendar, &UrgencyThresholds::default())
+        );
+    }
+
+    #[test]
+    fn test_badge_basis_can_change_color() {
+        let t = DateTime::parse_from_rfc3339("1999-05-17T12:00:00+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        assert!(coordinates
+            .to_shields_badge_url(BadgeBasis::Calendar, &UrgencyThresholds::default())
+            .ends_with("-yellow"));
+        assert!(coordinates
+            .to_shields_badge_url(BadgeBasis::Business, &UrgencyThresholds::default())
+            .ends_with("-green"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_to_json() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let json = generate_coordinates(&t).to_json();
+        assert!(json.contains("\"quarter\": 2,"));
+        assert!(json.contains("\"days_left_in_quarter\": 45,"));
+        assert!(json.contains("\"risk_level\": \"ON TRACK\""));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_base64() {
+        use base64::Engine;
+
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let encoded = generate_coordinates(&t).to_json_base64();
+        assert!(!encoded.contains(['\n', '"']));
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&decoded).unwrap();
+        assert_eq!(2, json["quarter"]);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_all_quarters_json_array() {
+        let utc = chrono::FixedOffset::east_opt(0).unwrap();
+        let quarters = crate::generate_year_coordinates(2024, utc).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&to_json_array(&quarters)).unwrap();
+        let quarters: Vec<u64> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|q| q["quarter"].as_u64().unwrap())
+            .collect();
+        assert_eq!(vec![1, 2, 3, 4], quarters);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_to_yaml() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let yaml = generate_coordinates(&t).to_yaml();
+        assert!(yaml.contains("generation_time: 1999-05-16T16:39:57Z"));
+        assert!(yaml.contains("quarter: 2\n"));
+        assert!(yaml.contains("days_left_in_quarter: 45\n"));
+        assert!(yaml.ends_with("risk_level: ON TRACK\n"));
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn test_ron_round_trip() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+01:00").unwrap();
+        let ron = generate_coordinates(&t).to_ron();
+        assert!(ron.contains("quarter: 2,"));
+
+        let parsed = CorporateCoordinates::from_ron(&ron).unwrap();
+        assert_eq!(t, parsed.generation_time);
+        assert_eq!(ron, parsed.to_ron());
+    }
+}
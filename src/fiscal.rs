@@ -0,0 +1,388 @@
+use chrono::prelude::*;
+use chrono::Months;
+use clap::ValueEnum;
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::{
+    generate_fiscal_coordinates, generate_fiscal_coordinates_with_naming, CorporateCoordinates,
+};
+
+/// Well-known fiscal years, by the month they start in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FiscalPreset {
+    /// Starts in January, the same as the calendar year
+    Calendar,
+    /// Starts in April and is named after the year it starts in, as in Japan
+    Japan,
+    /// Starts in July, as in Australia
+    Australia,
+    /// Starts in October, as for the US federal government
+    UsFederal,
+}
+
+impl FiscalPreset {
+    pub fn start_month(&self) -> u32 {
+        match self {
+            FiscalPreset::Calendar => 1,
+            FiscalPreset::Japan => 4,
+            FiscalPreset::Australia => 7,
+            FiscalPreset::UsFederal => 10,
+        }
+    }
+
+    /// Which year the preset's fiscal years are named after.
+    pub fn year_naming(&self) -> FiscalYearNaming {
+        match self {
+            FiscalPreset::Japan => FiscalYearNaming::StartYear,
+            _ => FiscalYearNaming::EndYear,
+        }
+    }
+
+    /// The coordinates of `now` in this preset's fiscal year.
+    pub fn coordinates(&self, now: &DateTime<FixedOffset>) -> CorporateCoordinates {
+        generate_fiscal_coordinates_with_naming(now, self.start_month(), self.year_naming())
+    }
+}
+
+/// Which calendar year a fiscal year that doesn't start in January is named
+/// after.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FiscalYearNaming {
+    /// The year it ends in, e.g. US federal FY2026 starts in October 2025
+    #[default]
+    EndYear,
+    /// The year it starts in, e.g. Japanese FY2025 starts in April 2025
+    StartYear,
+}
+
+/// A labelled fiscal calendar, e.g. one subsidiary's.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiscalStart {
+    pub label: String,
+    /// The month (1-12) the fiscal year starts in.
+    pub month: u32,
+    pub year_naming: FiscalYearNaming,
+}
+
+impl FiscalStart {
+    /// The coordinates of `now` in this calendar.
+    pub fn coordinates(&self, now: &DateTime<FixedOffset>) -> CorporateCoordinates {
+        generate_fiscal_coordinates_with_naming(now, self.month, self.year_naming)
+    }
+}
+
+impl FromStr for FiscalStart {
+    type Err = String;
+
+    /// Parses `LABEL=MONTH`, where `MONTH` is a number (1-12) or a preset name
+    /// such as `japan`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (label, month) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected LABEL=MONTH, got {:?}", s))?;
+        let (month, year_naming) = match month.parse::<u32>() {
+            Ok(month @ 1..=12) => (month, FiscalYearNaming::default()),
+            Ok(_) => return Err(format!("{} is not a month from 1 to 12", month)),
+            Err(_) => {
+                let preset = FiscalPreset::from_str(month, true)
+                    .map_err(|_| format!("{} is not a month or fiscal preset", month))?;
+                (preset.start_month(), preset.year_naming())
+            }
+        };
+        Ok(FiscalStart {
+            label: label.to_string(),
+            month,
+            year_naming,
+        })
+    }
+}
+
+/// How years are written in human-readable output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Era {
+    /// Gregorian years, e.g. 2025
+    #[default]
+    Gregorian,
+    /// Japanese era years, e.g. 令和7年
+    Japanese,
+}
+
+const JAPANESE_ERAS: [(&str, i32, u32, u32); 4] = [
+    ("令和", 2019, 5, 1),
+    ("平成", 1989, 1, 8),
+    ("昭和", 1926, 12, 25),
+    ("大正", 1912, 7, 30),
+];
+
+/// The Japanese era in effect on `date` and the year within it, or `None`
+/// for dates before the Taishō era.
+pub fn japanese_era_year(date: NaiveDate) -> Option<(&'static str, i32)> {
+    JAPANESE_ERAS
+        .iter()
+        .find(|(_, year, month, day)| date >= NaiveDate::from_ymd_opt(*year, *month, *day).unwrap())
+        .map(|(name, year, _, _)| (*name, date.year() - year + 1))
+}
+
+impl CorporateCoordinates {
+    /// Which year the fiscal year is named after, as generated.
+    #[must_use]
+    pub fn fiscal_year_naming(&self) -> FiscalYearNaming {
+        let start_year = self.start_of_fiscal_year().year().to_string();
+        if self.fiscal_start_month != 1 && self.year == start_year {
+            FiscalYearNaming::StartYear
+        } else {
+            FiscalYearNaming::EndYear
+        }
+    }
+
+    /// The date the fiscal year containing these coordinates starts.
+    #[must_use]
+    pub fn start_of_fiscal_year(&self) -> NaiveDate {
+        self.start_of_quarter
+            .date_naive()
+            .checked_sub_months(Months::new((self.quarter - 1) * 3))
+            .unwrap()
+    }
+
+    /// The week (1-53) of the fiscal year, with week 1 starting on the first
+    /// day of the fiscal year.
+    #[must_use]
+    pub fn fiscal_week_of_year(&self) -> u32 {
+        let days = self
+            .generation_time
+            .date_naive()
+            .signed_duration_since(self.start_of_fiscal_year())
+            .num_days();
+        days as u32 / 7 + 1
+    }
+
+    /// A code like `FY24W23D3`: the fiscal year's last two digits, the fiscal
+    /// week and the ISO weekday (Monday is 1).
+    #[must_use]
+    pub fn day_code(&self, code: DayCode) -> String {
+        match code {
+            DayCode::Fiscal => format!(
+                "FY{}W{:02}D{}",
+                &self.year[self.year.len().saturating_sub(2)..],
+                self.fiscal_week_of_year(),
+                self.generation_time.weekday().number_from_monday()
+            ),
+        }
+    }
+
+    /// The fiscal year as a Japanese era year: the era in effect on the day
+    /// it starts, and the year within it matching the fiscal year's name, e.g.
+    /// `令和7年度` for fiscal 2025, starting April 2025, or `令和7年` for a
+    /// calendar year.
+    #[must_use]
+    pub fn japanese_era_label(&self) -> Option<String> {
+        let start = self.start_of_fiscal_year();
+        let (era, year) = japanese_era_year(start)?;
+        let year = year + self.year.parse::<i32>().ok()? - start.year();
+        let year = if year == 1 {
+            "元".to_string()
+        } else {
+            year.to_string()
+        };
+        let suffix = if self.fiscal_start_month == 1 {
+            "年"
+        } else {
+            "年度"
+        };
+        Some(format!("{}{}{}", era, year, suffix))
+    }
+}
+
+/// Compact codes identifying the current day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DayCode {
+    /// Fiscal year, week and weekday, e.g. FY24W23D3
+    Fiscal,
+}
+
+/// A fiscal calendar fixed at compile time.
+pub trait FiscalCalendar {
+    /// The month (1-12) the fiscal year starts in.
+    const FISCAL_START_MONTH: u8;
+}
+
+/// Fiscal years that match calendar years.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StandardCalendar;
+
+impl FiscalCalendar for StandardCalendar {
+    const FISCAL_START_MONTH: u8 = 1;
+}
+
+/// Fiscal years that start in October.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OctoberFiscalCalendar;
+
+impl FiscalCalendar for OctoberFiscalCalendar {
+    const FISCAL_START_MONTH: u8 = 10;
+}
+
+/// Coordinates tagged with the fiscal calendar they were generated for, so
+/// that coordinates from different calendars can't be compared by mistake.
+pub struct FiscalCoordinates<Cal: FiscalCalendar> {
+    coordinates: CorporateCoordinates,
+    calendar: PhantomData<Cal>,
+}
+
+impl<Cal: FiscalCalendar> FiscalCoordinates<Cal> {
+    pub fn generate(now: &DateTime<FixedOffset>) -> Self {
+        FiscalCoordinates {
+            coordinates: generate_fiscal_coordinates(now, Cal::FISCAL_START_MONTH as u32),
+            calendar: PhantomData,
+        }
+    }
+
+    /// Drops the calendar tag.
+    #[must_use]
+    pub fn into_inner(self) -> CorporateCoordinates {
+        self.coordinates
+    }
+}
+
+impl<Cal: FiscalCalendar> Deref for FiscalCoordinates<Cal> {
+    type Target = CorporateCoordinates;
+
+    fn deref(&self) -> &CorporateCoordinates {
+        &self.coordinates
+    }
+}
+
+impl<Cal: FiscalCalendar> PartialEq for FiscalCoordinates<Cal> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coordinates == other.coordinates
+    }
+}
+
+impl<Cal: FiscalCalendar> Eq for FiscalCoordinates<Cal> {}
+
+impl<Cal: FiscalCalendar> PartialOrd for FiscalCoordinates<Cal> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Cal: FiscalCalendar> Ord for FiscalCoordinates<Cal> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.coordinates.cmp(&other.coordinates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_japan_preset_starts_in_april() {
+        let april = DateTime::parse_from_rfc3339("2025-04-01T09:00:00+09:00").unwrap();
+        let march = DateTime::parse_from_rfc3339("2026-03-31T09:00:00+09:00").unwrap();
+        let start = FiscalPreset::Japan.start_month();
+        assert_eq!(1, generate_fiscal_coordinates(&april, start).quarter);
+        assert_eq!(4, generate_fiscal_coordinates(&march, start).quarter);
+    }
+
+    #[test]
+    fn test_fiscal_day_code() {
+        // Japanese fiscal 2025 starts on Tuesday 1st April.
+        let t = DateTime::parse_from_rfc3339("2025-11-15T09:00:00+09:00").unwrap();
+        let coordinates = FiscalPreset::Japan.coordinates(&t);
+        assert_eq!(33, coordinates.fiscal_week_of_year());
+        assert_eq!("FY25W33D6", coordinates.day_code(DayCode::Fiscal));
+
+        let t = DateTime::parse_from_rfc3339("2024-01-03T09:00:00+00:00").unwrap();
+        assert_eq!(
+            "FY24W01D3",
+            generate_fiscal_coordinates(&t, 1).day_code(DayCode::Fiscal)
+        );
+    }
+
+    #[test]
+    fn test_fiscal_starts_for_the_same_date() {
+        let t = DateTime::parse_from_rfc3339("1999-11-15T16:39:57+00:00").unwrap();
+        let parent: FiscalStart = "Parent=1".parse().unwrap();
+        let subsidiary: FiscalStart = "Subsidiary=us-federal".parse().unwrap();
+        assert_eq!(10, subsidiary.month);
+        assert_eq!(4, parent.coordinates(&t).quarter);
+        assert_eq!(1, subsidiary.coordinates(&t).quarter);
+
+        assert!("Parent".parse::<FiscalStart>().is_err());
+        assert!("Parent=13".parse::<FiscalStart>().is_err());
+    }
+
+    #[test]
+    fn test_japanese_era_year() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(Some(("令和", 7)), japanese_era_year(date(2025, 4, 1)));
+        assert_eq!(Some(("令和", 1)), japanese_era_year(date(2019, 5, 1)));
+        assert_eq!(Some(("平成", 31)), japanese_era_year(date(2019, 4, 30)));
+        assert_eq!(Some(("昭和", 64)), japanese_era_year(date(1989, 1, 7)));
+        assert_eq!(None, japanese_era_year(date(1900, 1, 1)));
+    }
+
+    #[test]
+    fn test_japanese_era_label() {
+        let t = DateTime::parse_from_rfc3339("2025-11-15T09:00:00+09:00").unwrap();
+        assert_eq!(
+            Some("令和7年度".to_string()),
+            FiscalPreset::Japan.coordinates(&t).japanese_era_label()
+        );
+        assert_eq!(
+            Some("令和7年".to_string()),
+            generate_fiscal_coordinates(&t, 1).japanese_era_label()
+        );
+        // US federal fiscal 2026 starts in October 2025.
+        assert_eq!(
+            Some("令和8年度".to_string()),
+            generate_fiscal_coordinates(&t, 10).japanese_era_label()
+        );
+    }
+
+    #[test]
+    fn test_japanese_fiscal_year_across_april() {
+        for (now, year, era_year) in [
+            ("2025-03-31T09:00:00+09:00", "2024", "令和6年度"),
+            ("2025-04-01T09:00:00+09:00", "2025", "令和7年度"),
+            ("2026-03-31T09:00:00+09:00", "2025", "令和7年度"),
+        ] {
+            let t = DateTime::parse_from_rfc3339(now).unwrap();
+            let coordinates = FiscalPreset::Japan.coordinates(&t);
+            assert_eq!(year, coordinates.year);
+            assert_eq!(Some(era_year.to_string()), coordinates.japanese_era_label());
+            assert_eq!(
+                FiscalYearNaming::StartYear,
+                coordinates.fiscal_year_naming()
+            );
+            assert_eq!(
+                year,
+                coordinates.relative_quarter(4).relative_quarter(-4).year
+            );
+        }
+
+        // Other fiscal years starting in April keep the usual naming.
+        let t = DateTime::parse_from_rfc3339("2025-04-01T09:00:00+01:00").unwrap();
+        let april: FiscalStart = "UK=4".parse().unwrap();
+        assert_eq!("2026", april.coordinates(&t).year);
+        let japan: FiscalStart = "Tokyo=japan".parse().unwrap();
+        assert_eq!("2025", japan.coordinates(&t).year);
+    }
+
+    #[test]
+    fn test_typed_fiscal_coordinates() {
+        let t = DateTime::parse_from_rfc3339("1999-11-15T16:39:57+00:00").unwrap();
+        let later = DateTime::parse_from_rfc3339("1999-12-15T16:39:57+00:00").unwrap();
+        let october = FiscalCoordinates::<OctoberFiscalCalendar>::generate(&t);
+        assert_eq!((1, "2000"), (october.quarter, october.year.as_str()));
+        assert!(october < FiscalCoordinates::generate(&later));
+
+        let standard = FiscalCoordinates::<StandardCalendar>::generate(&t);
+        assert_eq!((4, "1999"), (standard.quarter, standard.year.as_str()));
+        assert_eq!(t, standard.into_inner().generation_time);
+    }
+}
@@ -0,0 +1,1595 @@
+use chrono::prelude::*;
+use chrono::{Days, Months};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::{fmt, fs};
+
+use crate::fiscal::FiscalYearNaming;
+
+pub mod batch;
+pub mod cache;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+pub mod compare;
+pub mod daycount;
+pub mod export;
+pub mod fiscal;
+pub mod planning;
+pub mod render;
+#[cfg(feature = "slack")]
+pub mod slack;
+pub mod zone;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CorporateCoordinates {
+    pub generation_time: DateTime<FixedOffset>,
+    pub year: String,
+    pub quarter: u32,
+    pub fiscal_start_month: u32,
+    pub start_of_quarter: DateTime<FixedOffset>,
+    pub end_of_quarter: DateTime<FixedOffset>,
+    pub full_week_of_quarter_done: u32,
+    pub day_of_quarter: u32,
+    pub business_day_of_quarter: u32,
+    pub weeks_in_quarter: u32,
+    pub days_left_in_quarter: u32,
+    pub days_remaining_after_today: u32,
+    pub days_in_quarter: u32,
+    pub percent_remaining: f64,
+    pub percent_elapsed: f64,
+    pub business_days_left_in_quarter: u32,
+    pub total_business_days_in_quarter: u32,
+    pub business_percent_remaining: f64,
+    /// The day of the quarter work is scheduled to be finished by, if any.
+    pub target_completion_day: Option<u32>,
+    /// The length of the team's sprints, if configured.
+    pub sprint_length_days: Option<u32>,
+}
+
+/// How a company's calendar differs from plain calendar quarters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalendarConfig {
+    /// The month (1-12) the fiscal year starts in.
+    pub fiscal_start_month: u32,
+    /// Which year fiscal years are named after.
+    pub fiscal_year_naming: FiscalYearNaming,
+    /// Reports this many weeks per quarter instead of the usual 13.
+    pub weeks_in_quarter_override: Option<u32>,
+    pub sprint_length_days: Option<u32>,
+    /// Days off that don't count as business days.
+    pub holiday_dates: Vec<NaiveDate>,
+}
+
+impl Default for CalendarConfig {
+    /// Plain calendar quarters of 13 weeks, with no sprints or holidays.
+    fn default() -> Self {
+        CalendarConfig {
+            fiscal_start_month: 1,
+            fiscal_year_naming: FiscalYearNaming::EndYear,
+            weeks_in_quarter_override: None,
+            sprint_length_days: None,
+            holiday_dates: Vec::new(),
+        }
+    }
+}
+
+/// The span of time the coordinates measure progress through.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Period {
+    #[default]
+    Quarter,
+    Month,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CoordinateError {
+    ParseError(chrono::ParseError),
+    TimestampOutOfRange(i64),
+    InvalidQuarter(String),
+    DeadlineInPast(NaiveDate),
+    OffsetOutOfRange(f32),
+    YearOutOfRange(i32),
+    InvalidConfig(String),
+    NowFile(PathBuf, String),
+}
+
+impl fmt::Display for CoordinateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoordinateError::ParseError(e) => write!(f, "could not parse date: {}", e),
+            CoordinateError::TimestampOutOfRange(ts) => {
+                write!(f, "timestamp {} is out of range", ts)
+            }
+            CoordinateError::InvalidQuarter(spec) => {
+                write!(f, "{} is not a quarter like Q2-2024", spec)
+            }
+            CoordinateError::DeadlineInPast(deadline) => {
+                write!(f, "the deadline {} has already passed", deadline)
+            }
+            CoordinateError::OffsetOutOfRange(hours) => {
+                write!(f, "UTC offset of {} hours is outside -18 to +18", hours)
+            }
+            CoordinateError::YearOutOfRange(year) => write!(f, "year {} is out of range", year),
+            CoordinateError::InvalidConfig(reason) => {
+                write!(f, "invalid calendar config: {}", reason)
+            }
+            CoordinateError::NowFile(path, reason) => write!(
+                f,
+                "could not read the current time from {}: {}",
+                path.display(),
+                reason
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CoordinateError {}
+
+impl From<chrono::ParseError> for CoordinateError {
+    fn from(e: chrono::ParseError) -> Self {
+        CoordinateError::ParseError(e)
+    }
+}
+
+impl CorporateCoordinates {
+    /// Generates the coordinates of a Unix timestamp in seconds at a UTC
+    /// offset of `offset_hours`, which may be fractional (5.5 for +05:30).
+    pub fn for_epoch(
+        epoch_seconds: i64,
+        offset_hours: f32,
+    ) -> Result<CorporateCoordinates, CoordinateError> {
+        if !(-18.0..=18.0).contains(&offset_hours) {
+            return Err(CoordinateError::OffsetOutOfRange(offset_hours));
+        }
+        let offset = FixedOffset::east_opt((offset_hours * 3600.0).round() as i32)
+            .ok_or(CoordinateError::OffsetOutOfRange(offset_hours))?;
+        let t = DateTime::from_timestamp(epoch_seconds, 0)
+            .ok_or(CoordinateError::TimestampOutOfRange(epoch_seconds))?;
+        Ok(generate_coordinates(&t.with_timezone(&offset)))
+    }
+
+    /// The quarter as prose, e.g. `Q2, 2024`.
+    #[must_use]
+    pub fn quarter_label(&self) -> String {
+        format!("Q{}, {}", self.quarter, self.year)
+    }
+
+    /// The quarter's number when the first quarter of the year is numbered
+    /// `first` rather than 1, e.g. 0 for quarters Q0 to Q3.
+    #[must_use]
+    pub fn quarter_number_from(&self, first: u32) -> u32 {
+        self.quarter - 1 + first
+    }
+
+    /// The quarter in a sortable ISO-like form, e.g. `2024-Q2`.
+    #[must_use]
+    pub fn quarter_iso_label(&self) -> String {
+        format!("{}-Q{}", self.year, self.quarter)
+    }
+
+    /// The fiscal year, quarter and fiscal start month, which are the same
+    /// for all coordinates in one quarter of one calendar.
+    #[must_use]
+    pub fn quarter_key(&self) -> QuarterKey {
+        QuarterKey {
+            year: self.year.clone(),
+            quarter: self.quarter,
+            fiscal_start_month: self.fiscal_start_month,
+        }
+    }
+
+    /// A compact identifier for the quarter, e.g. `2025Q2`.
+    #[must_use]
+    pub fn year_quarter_id(&self) -> String {
+        format!("{}Q{}", self.year, self.quarter)
+    }
+
+    /// The same quarter, but counting the time remaining up to `deadline`
+    /// instead of the end of the quarter. Business days are recounted without
+    /// holidays; use `CorporateCoordinatesBuilder::deadline` to keep them.
+    pub fn with_deadline(
+        &self,
+        deadline: NaiveDate,
+    ) -> Result<CorporateCoordinates, CoordinateError> {
+        if deadline < self.generation_time.date_naive() {
+            return Err(CoordinateError::DeadlineInPast(deadline));
+        }
+        let end = self
+            .generation_time
+            .offset()
+            .from_local_datetime(&deadline.and_hms_opt(0, 0, 0).unwrap())
+            .unwrap();
+        let mut coordinates = coordinates_for_quarter(
+            &self.generation_time,
+            self.year.clone(),
+            self.quarter,
+            self.fiscal_start_month,
+            self.start_of_quarter,
+            end,
+            self.weeks_in_quarter,
+        );
+        coordinates.target_completion_day = self.target_completion_day;
+        coordinates.sprint_length_days = self.sprint_length_days;
+        Ok(coordinates)
+    }
+
+    /// The coordinates at the start of the following quarter.
+    #[must_use]
+    pub fn next_quarter(&self) -> CorporateCoordinates {
+        generate_fiscal_coordinates_with_naming(
+            &self.end_of_quarter.checked_add_days(Days::new(1)).unwrap(),
+            self.fiscal_start_month,
+            self.fiscal_year_naming(),
+        )
+    }
+
+    /// The coordinates `offset` quarters before (negative) or after
+    /// (positive) these, at the same point in the quarter, keeping the
+    /// fiscal year start. The result is always a whole quarter, even if these
+    /// coordinates are for a month.
+    #[must_use]
+    pub fn relative_quarter(&self, offset: i32) -> CorporateCoordinates {
+        let months = Months::new(offset.unsigned_abs() * 3);
+        let shifted = if offset < 0 {
+            self.generation_time.checked_sub_months(months)
+        } else {
+            self.generation_time.checked_add_months(months)
+        };
+        generate_fiscal_coordinates_with_naming(
+            &shifted.unwrap(),
+            self.fiscal_start_month,
+            self.fiscal_year_naming(),
+        )
+    }
+
+    /// The coordinates at the start of each of the `count` quarters after
+    /// this one.
+    #[must_use]
+    pub fn upcoming_quarters(&self, count: usize) -> Vec<CorporateCoordinates> {
+        let mut quarters: Vec<CorporateCoordinates> = Vec::with_capacity(count);
+        for _ in 0..count {
+            let next = quarters.last().unwrap_or(self).next_quarter();
+            quarters.push(next);
+        }
+        quarters
+    }
+
+    /// Calendar days from the generation time until the start of `other`.
+    #[must_use]
+    pub fn days_until_start_of(&self, other: &CorporateCoordinates) -> i64 {
+        other
+            .start_of_quarter
+            .date_naive()
+            .signed_duration_since(self.generation_time.date_naive())
+            .num_days()
+    }
+
+    /// Signed days from the generation date to `target`: positive when
+    /// `target` is in the future, negative when it is in the past.
+    #[must_use]
+    pub fn days_to_date(&self, target: NaiveDate) -> i32 {
+        target
+            .signed_duration_since(self.generation_time.date_naive())
+            .num_days() as i32
+    }
+
+    /// Whether the generation time is on the last day of the quarter.
+    #[must_use]
+    pub fn is_last_day_of_quarter(&self) -> bool {
+        self.days_remaining_after_today == 0
+    }
+
+    /// The coordinates as of the close of business today: generated at
+    /// 23:59:59, with all of today counted as elapsed, so only the days after
+    /// today are left. Business day counts are unchanged.
+    #[must_use]
+    pub fn as_of_end_of_day(mut self) -> CorporateCoordinates {
+        self.generation_time = end_of_day(&self.generation_time);
+        self.days_left_in_quarter = self.days_remaining_after_today;
+        self.percent_remaining =
+            percent_of_quarter(self.days_left_in_quarter, self.days_in_quarter);
+        self.percent_elapsed = 100.0 - self.percent_remaining;
+        self
+    }
+
+    /// The percentage of the quarter remaining at `instant` rather than at
+    /// the generation time, using the same bounds.
+    #[must_use]
+    pub fn percent_remaining_at(&self, instant: DateTime<FixedOffset>) -> f64 {
+        percent_of_quarter(
+            days_left_until(&self.end_of_quarter, &instant),
+            self.days_in_quarter,
+        )
+    }
+}
+
+impl PartialEq for CorporateCoordinates {
+    fn eq(&self, other: &Self) -> bool {
+        self.generation_time == other.generation_time
+    }
+}
+
+impl Eq for CorporateCoordinates {}
+
+impl PartialOrd for CorporateCoordinates {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Coordinates are ordered by the instant they were generated for.
+impl Ord for CorporateCoordinates {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.generation_time.cmp(&other.generation_time)
+    }
+}
+
+/// Hashes and compares coordinates by quarter, whenever in the quarter they
+/// were generated, e.g. as the key of a map grouping them by quarter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QuarterKey {
+    pub year: String,
+    pub quarter: u32,
+    pub fiscal_start_month: u32,
+}
+
+impl Hash for CorporateCoordinates {
+    /// Hashes the instant the coordinates were generated for. `Eq` compares
+    /// that instant, and equal values must hash alike, so coordinates can't
+    /// hash by quarter themselves; use `quarter_key` for that.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.generation_time.hash(state);
+    }
+}
+
+impl TryFrom<&str> for CorporateCoordinates {
+    type Error = CoordinateError;
+
+    /// Parses an RFC 3339 timestamp and generates its coordinates.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(generate_coordinates(&DateTime::parse_from_rfc3339(value)?))
+    }
+}
+
+impl TryFrom<i64> for CorporateCoordinates {
+    type Error = CoordinateError;
+
+    /// Generates the coordinates, in UTC, of a Unix timestamp in seconds.
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        let t = DateTime::from_timestamp(value, 0)
+            .ok_or(CoordinateError::TimestampOutOfRange(value))?;
+        Ok(generate_coordinates(&t.fixed_offset()))
+    }
+}
+
+/// Returns the fiscal quarter (1-4) that calendar `month` (1-12) falls in, for
+/// a fiscal year starting in `fiscal_start` (1-12).
+pub const fn fiscal_quarter_of_month(month: u8, fiscal_start: u8) -> u8 {
+    (month + 12 - fiscal_start) % 12 / 3 + 1
+}
+
+pub fn generate_coordinates(now: &DateTime<FixedOffset>) -> CorporateCoordinates {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("generate_coordinates", %now).entered();
+    let coordinates = generate_coordinates_with_config(now, &CalendarConfig::default())
+        .expect("the default calendar config is valid");
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        quarter = coordinates.quarter,
+        days_left = coordinates.days_left_in_quarter,
+        "generated coordinates"
+    );
+    coordinates
+}
+
+/// Generates the coordinates of `now` under `config`, leaving holidays out of
+/// the business day counts.
+pub fn generate_coordinates_with_config(
+    now: &DateTime<FixedOffset>,
+    config: &CalendarConfig,
+) -> Result<CorporateCoordinates, CoordinateError> {
+    validate_config(config)?;
+    Ok(apply_config(
+        generate_fiscal_coordinates_with_naming(
+            now,
+            config.fiscal_start_month,
+            config.fiscal_year_naming,
+        ),
+        config,
+    ))
+}
+
+fn validate_config(config: &CalendarConfig) -> Result<(), CoordinateError> {
+    if !(1..=12).contains(&config.fiscal_start_month) {
+        return Err(CoordinateError::InvalidConfig(format!(
+            "fiscal start month {} is not from 1 to 12",
+            config.fiscal_start_month
+        )));
+    }
+    if config.weeks_in_quarter_override == Some(0) {
+        return Err(CoordinateError::InvalidConfig(
+            "a quarter can't have zero weeks".to_string(),
+        ));
+    }
+    if config.sprint_length_days == Some(0) {
+        return Err(CoordinateError::InvalidConfig(
+            "a sprint can't be zero days long".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Applies `config`'s weeks, sprints and holidays to coordinates generated
+/// for its fiscal year. Holidays come off the business day counts up to
+/// `end_of_quarter`, so a deadline must be set before this.
+fn apply_config(
+    mut coordinates: CorporateCoordinates,
+    config: &CalendarConfig,
+) -> CorporateCoordinates {
+    if let Some(weeks) = config.weeks_in_quarter_override {
+        coordinates.weeks_in_quarter = weeks;
+    }
+    coordinates.sprint_length_days = config.sprint_length_days;
+
+    let holidays_between = |from: DateTime<FixedOffset>, to: DateTime<FixedOffset>| {
+        let (from, to) = (from.date_naive(), to.date_naive());
+        let mut holidays: Vec<&NaiveDate> = config
+            .holiday_dates
+            .iter()
+            .filter(|d| from <= **d && **d <= to && d.weekday().num_days_from_monday() < 5)
+            .collect();
+        holidays.sort_unstable();
+        holidays.dedup();
+        holidays.len() as u32
+    };
+    let now = coordinates.generation_time;
+    let (start, end) = (coordinates.start_of_quarter, coordinates.end_of_quarter);
+    coordinates.business_days_left_in_quarter -= holidays_between(now, end);
+    coordinates.total_business_days_in_quarter -= holidays_between(start, end);
+    coordinates.business_day_of_quarter -= holidays_between(start, now);
+    coordinates.business_percent_remaining = coordinates.business_days_left_in_quarter as f64
+        / coordinates.total_business_days_in_quarter as f64
+        * 100.0;
+    coordinates
+}
+
+/// Generates coordinates for a fiscal year starting on the first of
+/// `fiscal_start_month` (1-12). Fiscal years are named after the calendar
+/// year they end in.
+pub fn generate_fiscal_coordinates(
+    now: &DateTime<FixedOffset>,
+    fiscal_start_month: u32,
+) -> CorporateCoordinates {
+    generate_fiscal_coordinates_with_naming(now, fiscal_start_month, FiscalYearNaming::EndYear)
+}
+
+/// Generates coordinates for a fiscal year starting on the first of
+/// `fiscal_start_month` (1-12), named after the year chosen by `naming`.
+pub fn generate_fiscal_coordinates_with_naming(
+    now: &DateTime<FixedOffset>,
+    fiscal_start_month: u32,
+    naming: FiscalYearNaming,
+) -> CorporateCoordinates {
+    let quarter = fiscal_quarter_of_month(now.month() as u8, fiscal_start_month as u8) as u32;
+    let start_of_fiscal_year_year = if now.month() >= fiscal_start_month {
+        now.year()
+    } else {
+        now.year() - 1
+    };
+    let start_of_year = NaiveDate::from_ymd_opt(start_of_fiscal_year_year, fiscal_start_month, 1)
+        .unwrap()
+        .and_hms_nano_opt(0, 0, 0, 0)
+        .unwrap();
+    let start_of_quarter = now
+        .offset()
+        .from_local_datetime(
+            &start_of_year
+                .checked_add_months(Months::new((quarter - 1) * 3))
+                .unwrap(),
+        )
+        .unwrap();
+
+    let end_of_quarter = now
+        .offset()
+        .from_local_datetime(
+            &start_of_year
+                .checked_add_months(Months::new((quarter) * 3))
+                .unwrap()
+                .checked_sub_days(Days::new(1))
+                .unwrap(),
+        )
+        .unwrap();
+
+    let fiscal_year = if fiscal_start_month == 1 || naming == FiscalYearNaming::StartYear {
+        start_of_fiscal_year_year
+    } else {
+        start_of_fiscal_year_year + 1
+    };
+
+    coordinates_for_quarter(
+        now,
+        fiscal_year.to_string(),
+        quarter,
+        fiscal_start_month,
+        start_of_quarter,
+        end_of_quarter,
+        13,
+    )
+}
+
+/// Generates coordinates using ISO week-based quarters: each quarter is 13
+/// ISO weeks, starting on the Monday of ISO week 1, with the extra week of a
+/// 53-week ISO year added to Q4.
+pub fn generate_iso_week_coordinates(now: &DateTime<FixedOffset>) -> CorporateCoordinates {
+    let iso_week = now.iso_week();
+    let quarter = ((iso_week.week() - 1) / 13 + 1).min(4);
+    let weeks_in_year = if NaiveDate::from_isoywd_opt(iso_week.year(), 53, Weekday::Mon).is_some() {
+        53
+    } else {
+        52
+    };
+    let first_week = (quarter - 1) * 13 + 1;
+    let last_week = if quarter == 4 {
+        weeks_in_year
+    } else {
+        quarter * 13
+    };
+    let day = |week, weekday| {
+        now.offset()
+            .from_local_datetime(
+                &NaiveDate::from_isoywd_opt(iso_week.year(), week, weekday)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+            .unwrap()
+    };
+
+    coordinates_for_quarter(
+        now,
+        iso_week.year().to_string(),
+        quarter,
+        1,
+        day(first_week, Weekday::Mon),
+        day(last_week, Weekday::Sun),
+        last_week - first_week + 1,
+    )
+}
+
+/// Generates coordinates treating `now`'s calendar month as the period, from
+/// its first day to midnight on its last, with `quarter` still the calendar
+/// quarter the month is in.
+pub fn generate_month_coordinates(now: &DateTime<FixedOffset>) -> CorporateCoordinates {
+    let start = now.date_naive().with_day(1).unwrap();
+    let last_day = start
+        .checked_add_months(Months::new(1))
+        .unwrap()
+        .pred_opt()
+        .unwrap();
+    let midnight = |date: NaiveDate| {
+        now.offset()
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+            .unwrap()
+    };
+    coordinates_for_quarter(
+        now,
+        now.year().to_string(),
+        fiscal_quarter_of_month(now.month() as u8, 1) as u32,
+        1,
+        midnight(start),
+        midnight(last_day),
+        last_day.day() / 7,
+    )
+}
+
+fn coordinates_for_quarter(
+    now: &DateTime<FixedOffset>,
+    year: String,
+    quarter: u32,
+    fiscal_start_month: u32,
+    start_of_quarter: DateTime<FixedOffset>,
+    end_of_quarter: DateTime<FixedOffset>,
+    weeks_in_quarter: u32,
+) -> CorporateCoordinates {
+    let days_left_in_quarter = days_left_until(&end_of_quarter, now);
+    let days_in_quarter = end_of_quarter
+        .signed_duration_since(start_of_quarter)
+        .num_days() as u32;
+    let percent_remaining = percent_of_quarter(days_left_in_quarter, days_in_quarter);
+    let business_days_left_in_quarter =
+        business_days_between(now.date_naive(), end_of_quarter.date_naive());
+    let total_business_days_in_quarter =
+        business_days_between(start_of_quarter.date_naive(), end_of_quarter.date_naive());
+
+    CorporateCoordinates {
+        generation_time: *now,
+        year,
+        quarter,
+        fiscal_start_month,
+        start_of_quarter,
+        end_of_quarter,
+        full_week_of_quarter_done: (now.signed_duration_since(start_of_quarter).num_days() as f64
+            / 7.0)
+            .floor() as u32,
+        weeks_in_quarter,
+        day_of_quarter: now
+            .date_naive()
+            .signed_duration_since(start_of_quarter.date_naive())
+            .num_days() as u32
+            + 1,
+        business_day_of_quarter: business_days_between(
+            start_of_quarter.date_naive(),
+            now.date_naive(),
+        ),
+        days_left_in_quarter,
+        days_remaining_after_today: end_of_quarter
+            .date_naive()
+            .signed_duration_since(now.date_naive())
+            .num_days() as u32,
+        days_in_quarter,
+        percent_remaining,
+        percent_elapsed: 100.0 - percent_remaining,
+        business_days_left_in_quarter,
+        total_business_days_in_quarter,
+        business_percent_remaining: (business_days_left_in_quarter as f64
+            / total_business_days_in_quarter as f64)
+            * 100.0,
+        target_completion_day: None,
+        sprint_length_days: None,
+    }
+}
+
+/// Days left before `end_of_quarter`, counting the day of `now`.
+fn days_left_until(end_of_quarter: &DateTime<FixedOffset>, now: &DateTime<FixedOffset>) -> u32 {
+    (end_of_quarter.signed_duration_since(now).num_days() + 1) as u32
+}
+
+/// `days` as a percentage of the quarter, capped at 100% for the first day,
+/// which counts `days_in_quarter + 1` days left at midnight.
+fn percent_of_quarter(days: u32, days_in_quarter: u32) -> f64 {
+    ((days as f64 / days_in_quarter as f64) * 100.0).min(100.0)
+}
+
+/// The local start and end (as midnight on the last day) of each calendar
+/// quarter of `year`.
+pub fn quarter_boundaries(year: i32) -> [(NaiveDateTime, NaiveDateTime); 4] {
+    let start_of_year = NaiveDate::from_ymd_opt(year, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    std::array::from_fn(|quarter| {
+        let start = start_of_year
+            .checked_add_months(Months::new(quarter as u32 * 3))
+            .unwrap();
+        let end = start
+            .checked_add_months(Months::new(3))
+            .unwrap()
+            .checked_sub_days(Days::new(1))
+            .unwrap();
+        (start, end)
+    })
+}
+
+/// Counts the weekdays (Monday to Friday) from `from` to `to`, inclusive.
+pub fn business_days_between(from: NaiveDate, to: NaiveDate) -> u32 {
+    from.iter_days()
+        .take_while(|d| *d <= to)
+        .filter(|d| d.weekday().num_days_from_monday() < 5)
+        .count() as u32
+}
+
+/// Builds coordinates with settings beyond the generation time.
+#[derive(Clone, Debug, Default)]
+pub struct CorporateCoordinatesBuilder {
+    now: Option<DateTime<FixedOffset>>,
+    config: CalendarConfig,
+    target_completion_day: Option<u32>,
+    deadline: Option<NaiveDate>,
+}
+
+impl CorporateCoordinatesBuilder {
+    /// A builder for the calendar-year coordinates of `now`.
+    pub fn new(now: DateTime<FixedOffset>) -> Self {
+        Self::default().now(now)
+    }
+
+    /// Generates the coordinates of `now` instead of the current local time.
+    pub fn now(mut self, now: DateTime<FixedOffset>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Uses a fiscal year starting in `month` (1-12).
+    pub fn fiscal_start_month(mut self, month: u32) -> Self {
+        self.config.fiscal_start_month = month;
+        self
+    }
+
+    /// Names fiscal years after the year chosen by `naming`.
+    pub fn fiscal_year_naming(mut self, naming: FiscalYearNaming) -> Self {
+        self.config.fiscal_year_naming = naming;
+        self
+    }
+
+    /// Schedules work to be finished by `day` of the quarter.
+    pub fn target_completion_day(mut self, day: u32) -> Self {
+        self.target_completion_day = Some(day);
+        self
+    }
+
+    /// Counts the time remaining up to `deadline`, as `with_deadline` does,
+    /// but still leaves the calendar's holidays out of the business days.
+    pub fn deadline(mut self, deadline: NaiveDate) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn build(self) -> Result<CorporateCoordinates, CoordinateError> {
+        let now = self.now.unwrap_or_else(|| local_to_fixed(&Local::now()));
+        validate_config(&self.config)?;
+        let mut coordinates = generate_fiscal_coordinates_with_naming(
+            &now,
+            self.config.fiscal_start_month,
+            self.config.fiscal_year_naming,
+        );
+        if let Some(deadline) = self.deadline {
+            coordinates = coordinates.with_deadline(deadline)?;
+        }
+        let mut coordinates = apply_config(coordinates, &self.config);
+        coordinates.target_completion_day = self.target_completion_day;
+        Ok(coordinates)
+    }
+}
+
+impl From<CalendarConfig> for CorporateCoordinatesBuilder {
+    /// A builder for the current local time under `config`.
+    fn from(config: CalendarConfig) -> Self {
+        CorporateCoordinatesBuilder {
+            config,
+            ..Self::default()
+        }
+    }
+}
+
+/// Generates the coordinates at the start of `quarter` (1-4) of `year`, in
+/// the time zone `offset`.
+pub fn generate_coordinates_for_quarter(
+    year: i32,
+    quarter: u32,
+    offset: FixedOffset,
+) -> Result<CorporateCoordinates, CoordinateError> {
+    let invalid = || CoordinateError::InvalidQuarter(format!("Q{}-{}", quarter, year));
+    if !(1..=4).contains(&quarter) {
+        return Err(invalid());
+    }
+    let start = NaiveDate::from_ymd_opt(year, (quarter - 1) * 3 + 1, 1)
+        .ok_or_else(invalid)?
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    Ok(generate_coordinates(
+        &offset.from_local_datetime(&start).unwrap(),
+    ))
+}
+
+/// Generates the coordinates at the start of each calendar quarter of `year`,
+/// in the time zone `offset`.
+pub fn generate_year_coordinates(
+    year: i32,
+    offset: FixedOffset,
+) -> Result<[CorporateCoordinates; 4], CoordinateError> {
+    if NaiveDate::from_ymd_opt(year, 12, 31).is_none() {
+        return Err(CoordinateError::YearOutOfRange(year));
+    }
+    Ok(quarter_boundaries(year)
+        .map(|(start, _)| generate_coordinates(&offset.from_local_datetime(&start).unwrap())))
+}
+
+/// Reads the current time from the RFC 3339 timestamp in the file at `path`,
+/// for simulations that advance time by rewriting the file.
+pub fn read_now_file(path: &Path) -> Result<DateTime<FixedOffset>, CoordinateError> {
+    let error = |reason: String| CoordinateError::NowFile(path.to_path_buf(), reason);
+    let contents = fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+    DateTime::parse_from_rfc3339(contents.trim()).map_err(|e| {
+        error(format!(
+            "{:?} is not an RFC 3339 timestamp ({})",
+            contents.trim(),
+            e
+        ))
+    })
+}
+
+pub fn local_to_fixed(local_date_time: &DateTime<Local>) -> DateTime<FixedOffset> {
+    local_date_time.with_timezone(local_date_time.offset())
+}
+
+/// 23:59:59 on the date of `now`, in the same offset, for reporting as of
+/// close of business.
+pub fn end_of_day(now: &DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+    now.offset()
+        .from_local_datetime(&now.date_naive().and_hms_opt(23, 59, 59).unwrap())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<FixedOffset> {
+        local_to_fixed(&Local::now())
+    }
+
+    #[test]
+    fn test_generation_time() {
+        let t = now();
+        assert_eq!(t, generate_coordinates(&t).generation_time)
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        let t = DateTime::parse_from_rfc3339("1999-02-15T10:00:00+00:00").unwrap();
+        let coordinates = CorporateCoordinates::try_from("1999-02-15T10:00:00+00:00").unwrap();
+        assert_eq!(t, coordinates.generation_time);
+        assert_eq!(1, coordinates.quarter);
+
+        assert!(matches!(
+            CorporateCoordinates::try_from("not a date"),
+            Err(CoordinateError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_builder_from_config() {
+        let t = DateTime::parse_from_rfc3339("1999-11-15T16:39:57+00:00").unwrap();
+        let config = CalendarConfig {
+            fiscal_start_month: 10,
+            weeks_in_quarter_override: Some(12),
+            ..CalendarConfig::default()
+        };
+        let coordinates = CorporateCoordinatesBuilder::from(config.clone())
+            .now(t)
+            .build()
+            .unwrap();
+        assert_eq!(t, coordinates.generation_time);
+        assert_eq!(
+            (1, "2000"),
+            (coordinates.quarter, coordinates.year.as_str())
+        );
+        assert_eq!(12, coordinates.weeks_in_quarter);
+
+        let overridden = CorporateCoordinatesBuilder::from(config)
+            .now(t)
+            .fiscal_start_month(1)
+            .build()
+            .unwrap();
+        assert_eq!(4, overridden.quarter);
+        assert_eq!(12, overridden.weeks_in_quarter);
+
+        assert!(CorporateCoordinatesBuilder::new(t)
+            .fiscal_start_month(0)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_default_config_matches_calendar_quarters() {
+        let config = CalendarConfig::default();
+        assert_eq!(1, config.fiscal_start_month);
+        assert_eq!(None, config.weeks_in_quarter_override);
+        assert_eq!(None, config.sprint_length_days);
+        assert!(config.holiday_dates.is_empty());
+
+        let mut t = DateTime::parse_from_rfc3339("1999-12-25T16:39:57+05:00").unwrap();
+        while t.year() < 2001 {
+            let configured = generate_coordinates_with_config(&t, &config).unwrap();
+            let plain = generate_fiscal_coordinates(&t, 1);
+            assert_eq!(plain.to_env_vars(), configured.to_env_vars());
+            assert_eq!(
+                (
+                    plain.weeks_in_quarter,
+                    plain.business_day_of_quarter,
+                    plain.business_days_left_in_quarter,
+                    plain.total_business_days_in_quarter,
+                    plain.business_percent_remaining
+                ),
+                (
+                    configured.weeks_in_quarter,
+                    configured.business_day_of_quarter,
+                    configured.business_days_left_in_quarter,
+                    configured.total_business_days_in_quarter,
+                    configured.business_percent_remaining
+                )
+            );
+            t = t.checked_add_days(Days::new(1)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_generate_coordinates_with_config() {
+        let t = DateTime::parse_from_rfc3339("1999-05-17T16:39:57+00:00").unwrap();
+        let plain = generate_coordinates(&t);
+        let config = CalendarConfig {
+            fiscal_start_month: 4,
+            fiscal_year_naming: FiscalYearNaming::EndYear,
+            weeks_in_quarter_override: Some(12),
+            sprint_length_days: Some(14),
+            // Good Friday, a Saturday, and the late May bank holiday twice.
+            holiday_dates: ["1999-04-02", "1999-05-01", "1999-05-31", "1999-05-31"]
+                .iter()
+                .map(|d| d.parse().unwrap())
+                .collect(),
+        };
+        let configured = generate_coordinates_with_config(&t, &config).unwrap();
+        assert_eq!((1, "2000"), (configured.quarter, configured.year.as_str()));
+        let named_by_start = CalendarConfig {
+            fiscal_year_naming: FiscalYearNaming::StartYear,
+            ..config.clone()
+        };
+        assert_eq!(
+            "1999",
+            generate_coordinates_with_config(&t, &named_by_start)
+                .unwrap()
+                .year
+        );
+        assert_eq!(12, configured.weeks_in_quarter);
+        assert_eq!(Some(14), configured.sprint_length_days);
+        assert_eq!(
+            plain.total_business_days_in_quarter - 2,
+            configured.total_business_days_in_quarter
+        );
+        assert_eq!(
+            plain.business_days_left_in_quarter - 1,
+            configured.business_days_left_in_quarter
+        );
+        assert_eq!(
+            plain.business_day_of_quarter - 1,
+            configured.business_day_of_quarter
+        );
+
+        let deadline = NaiveDate::from_ymd_opt(1999, 6, 15).unwrap();
+        let before_deadline = CorporateCoordinatesBuilder::new(t)
+            .fiscal_start_month(4)
+            .deadline(deadline)
+            .build()
+            .unwrap();
+        let builder = CorporateCoordinatesBuilder {
+            now: Some(t),
+            config: config.clone(),
+            target_completion_day: None,
+            deadline: Some(deadline),
+        };
+        let with_holidays = builder.build().unwrap();
+        assert_eq!(deadline, with_holidays.end_of_quarter.date_naive());
+        assert_eq!(
+            before_deadline.total_business_days_in_quarter - 2,
+            with_holidays.total_business_days_in_quarter
+        );
+        assert_eq!(
+            before_deadline.business_days_left_in_quarter - 1,
+            with_holidays.business_days_left_in_quarter
+        );
+
+        let invalid = CalendarConfig {
+            fiscal_start_month: 13,
+            ..CalendarConfig::default()
+        };
+        assert!(matches!(
+            generate_coordinates_with_config(&t, &invalid),
+            Err(CoordinateError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_month_coordinates() {
+        let month =
+            |rfc3339| generate_month_coordinates(&DateTime::parse_from_rfc3339(rfc3339).unwrap());
+
+        // June has 30 days.
+        let start = month("1999-06-01T16:39:57+00:00");
+        assert_eq!(
+            (2, 1, 29),
+            (start.quarter, start.day_of_quarter, start.days_in_quarter)
+        );
+        assert_eq!(100.0, start.percent_remaining);
+        let mid = month("1999-06-15T16:39:57+00:00");
+        assert_eq!((15, 15), (mid.day_of_quarter, mid.days_left_in_quarter));
+        let end = month("1999-06-30T16:39:57+00:00");
+        assert_eq!((30, 1), (end.day_of_quarter, end.days_left_in_quarter));
+        assert_eq!(0, end.days_remaining_after_today);
+
+        // July has 31.
+        let start = month("1999-07-01T16:39:57+00:00");
+        assert_eq!(
+            (3, 1, 30),
+            (start.quarter, start.day_of_quarter, start.days_in_quarter)
+        );
+        assert_eq!(100.0, start.percent_remaining);
+        let mid = month("1999-07-16T16:39:57+00:00");
+        assert_eq!((16, 15), (mid.day_of_quarter, mid.days_left_in_quarter));
+        assert_eq!(50.0, mid.percent_remaining);
+        let end = month("1999-07-31T16:39:57+00:00");
+        assert_eq!((31, 1), (end.day_of_quarter, end.days_left_in_quarter));
+    }
+
+    #[test]
+    fn test_generate_year_coordinates() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let quarters = generate_year_coordinates(2024, utc).unwrap();
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            quarters.iter().map(|q| q.quarter).collect::<Vec<_>>()
+        );
+        assert!(quarters.iter().all(|q| q.year == "2024"));
+        assert_eq!(
+            Err(CoordinateError::YearOutOfRange(i32::MAX)),
+            generate_year_coordinates(i32::MAX, utc).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_read_now_file() {
+        let path = std::env::temp_dir().join(format!("corporateclock-now-{}", std::process::id()));
+        fs::write(&path, "1999-05-16T16:39:57+01:00\n").unwrap();
+        assert_eq!(
+            Ok(DateTime::parse_from_rfc3339("1999-05-16T16:39:57+01:00").unwrap()),
+            read_now_file(&path)
+        );
+
+        fs::write(&path, "next tuesday").unwrap();
+        assert!(matches!(
+            read_now_file(&path),
+            Err(CoordinateError::NowFile(_, reason)) if reason.contains("next tuesday")
+        ));
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            read_now_file(&path),
+            Err(CoordinateError::NowFile(p, _)) if p == path
+        ));
+    }
+
+    #[test]
+    fn test_for_epoch() {
+        // 2000-03-31T20:00:00Z is already April in India.
+        let coordinates = CorporateCoordinates::for_epoch(954532800, 5.5).unwrap();
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2000-04-01T01:30:00+05:30").unwrap(),
+            coordinates.generation_time
+        );
+        assert_eq!(
+            19800,
+            coordinates.generation_time.offset().local_minus_utc()
+        );
+        assert_eq!(2, coordinates.quarter);
+
+        assert_eq!(
+            1,
+            CorporateCoordinates::for_epoch(954532800, -8.0)
+                .unwrap()
+                .quarter
+        );
+        assert!(matches!(
+            CorporateCoordinates::for_epoch(954532800, 18.5),
+            Err(CoordinateError::OffsetOutOfRange(_))
+        ));
+        assert!(matches!(
+            CorporateCoordinates::for_epoch(i64::MAX, 0.0),
+            Err(CoordinateError::TimestampOutOfRange(i64::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_try_from_unix_timestamp() {
+        let coordinates = CorporateCoordinates::try_from(946684800i64).unwrap();
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2000-01-01T00:00:00+00:00").unwrap(),
+            coordinates.generation_time
+        );
+        assert_eq!(1, coordinates.quarter);
+
+        assert!(matches!(
+            CorporateCoordinates::try_from(i64::MAX),
+            Err(CoordinateError::TimestampOutOfRange(i64::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_hash_agrees_with_eq() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |coordinates: &CorporateCoordinates| {
+            let mut hasher = DefaultHasher::new();
+            coordinates.hash(&mut hasher);
+            hasher.finish()
+        };
+        // The same instant, seen from either side of a quarter boundary.
+        let q1 = generate_coordinates(
+            &DateTime::parse_from_rfc3339("1999-03-31T23:00:00-01:00").unwrap(),
+        );
+        let q2 = generate_coordinates(
+            &DateTime::parse_from_rfc3339("1999-04-01T00:00:00+00:00").unwrap(),
+        );
+        assert_ne!(q1.quarter, q2.quarter);
+        assert!(q1 == q2);
+        assert_eq!(hash(&q1), hash(&q2));
+    }
+
+    #[test]
+    fn test_quarter_key_groups_by_quarter() {
+        use std::collections::HashSet;
+
+        let at = |time: &str| DateTime::parse_from_rfc3339(time).unwrap();
+        let keys: HashSet<QuarterKey> = [
+            generate_coordinates(&at("1999-04-01T00:00:00+00:00")),
+            generate_coordinates(&at("1999-05-16T16:39:57+00:00")),
+            generate_coordinates(&at("1999-06-30T23:59:59+00:00")),
+        ]
+        .iter()
+        .map(CorporateCoordinates::quarter_key)
+        .collect();
+        assert_eq!(1, keys.len());
+
+        // The same quarter number and year in another calendar is another key.
+        let july = generate_fiscal_coordinates(&at("1998-08-16T16:39:57+00:00"), 7);
+        assert_eq!((1, "1999"), (july.quarter, july.year.as_str()));
+        let q1 = generate_coordinates(&at("1999-02-16T16:39:57+00:00"));
+        assert_ne!(q1.quarter_key(), july.quarter_key());
+    }
+
+    #[test]
+    fn test_ordered_by_generation_time() {
+        let mut coordinates: Vec<CorporateCoordinates> = [
+            "1999-08-01T16:39:57+00:00",
+            "1999-02-01T16:39:57+00:00",
+            "1999-05-01T16:39:57+01:00",
+            "1999-05-01T16:39:57+00:00",
+        ]
+        .iter()
+        .map(|t| CorporateCoordinates::try_from(*t).unwrap())
+        .collect();
+        coordinates.sort();
+
+        let sorted: Vec<String> = coordinates
+            .iter()
+            .map(|c| c.generation_time.to_rfc3339())
+            .collect();
+        assert_eq!(
+            vec![
+                "1999-02-01T16:39:57+00:00",
+                "1999-05-01T16:39:57+01:00",
+                "1999-05-01T16:39:57+00:00",
+                "1999-08-01T16:39:57+00:00",
+            ],
+            sorted
+        );
+    }
+
+    #[test]
+    fn test_year_correct() {
+        let t = now();
+        assert_eq!(format!("{}", t.year()), generate_coordinates(&t).year)
+    }
+
+    #[test]
+    fn test_quarters_correct() {
+        let q1_jan = DateTime::parse_from_rfc3339("1999-01-01T16:39:57+00:00").unwrap();
+        let q1_feb = DateTime::parse_from_rfc3339("1999-02-01T16:39:57+00:00").unwrap();
+        let q1_mar = DateTime::parse_from_rfc3339("1999-03-01T16:39:57+00:00").unwrap();
+
+        let q2_apr = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
+        let q2_may = DateTime::parse_from_rfc3339("1999-05-01T16:39:57+00:00").unwrap();
+        let q2_jun = DateTime::parse_from_rfc3339("1999-06-01T16:39:57+00:00").unwrap();
+
+        let q3_jul = DateTime::parse_from_rfc3339("1999-07-01T16:39:57+00:00").unwrap();
+        let q3_aug = DateTime::parse_from_rfc3339("1999-08-01T16:39:57+00:00").unwrap();
+        let q3_sep = DateTime::parse_from_rfc3339("1999-09-01T16:39:57+00:00").unwrap();
+
+        let q4_oct = DateTime::parse_from_rfc3339("1999-10-01T16:39:57+00:00").unwrap();
+        let q4_nov = DateTime::parse_from_rfc3339("1999-11-01T16:39:57+00:00").unwrap();
+        let q4_dec = DateTime::parse_from_rfc3339("1999-12-01T16:39:57+00:00").unwrap();
+
+        assert_eq!(1, generate_coordinates(&q1_jan).quarter);
+        assert_eq!(1, generate_coordinates(&q1_feb).quarter);
+        assert_eq!(1, generate_coordinates(&q1_mar).quarter);
+
+        assert_eq!(2, generate_coordinates(&q2_apr).quarter);
+        assert_eq!(2, generate_coordinates(&q2_may).quarter);
+        assert_eq!(2, generate_coordinates(&q2_jun).quarter);
+
+        assert_eq!(3, generate_coordinates(&q3_jul).quarter);
+        assert_eq!(3, generate_coordinates(&q3_aug).quarter);
+        assert_eq!(3, generate_coordinates(&q3_sep).quarter);
+
+        assert_eq!(4, generate_coordinates(&q4_oct).quarter);
+        assert_eq!(4, generate_coordinates(&q4_nov).quarter);
+        assert_eq!(4, generate_coordinates(&q4_dec).quarter);
+    }
+
+    #[test]
+    fn test_fiscal_quarter_of_month_january_start() {
+        let expected = [1, 1, 1, 2, 2, 2, 3, 3, 3, 4, 4, 4];
+        for (month, quarter) in (1..=12).zip(expected) {
+            assert_eq!(
+                quarter,
+                fiscal_quarter_of_month(month, 1),
+                "month {}",
+                month
+            );
+        }
+    }
+
+    #[test]
+    fn test_fiscal_quarter_of_month_october_start() {
+        let expected = [2, 2, 2, 3, 3, 3, 4, 4, 4, 1, 1, 1];
+        for (month, quarter) in (1..=12).zip(expected) {
+            assert_eq!(
+                quarter,
+                fiscal_quarter_of_month(month, 10),
+                "month {}",
+                month
+            );
+        }
+    }
+
+    #[test]
+    fn test_quarter_labels() {
+        let q2 = generate_coordinates(
+            &DateTime::parse_from_rfc3339("2024-05-01T16:39:57+00:00").unwrap(),
+        );
+        assert_eq!("Q2, 2024", q2.quarter_label());
+        assert_eq!("2024-Q2", q2.quarter_iso_label());
+        assert_eq!("2024Q2", q2.year_quarter_id());
+    }
+
+    #[test]
+    fn test_with_deadline() {
+        let mid_q2 = generate_coordinates(
+            &DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap(),
+        );
+        let deadline = NaiveDate::from_ymd_opt(1999, 5, 31).unwrap();
+        let with_deadline = mid_q2.with_deadline(deadline).unwrap();
+        assert_eq!(2, with_deadline.quarter);
+        assert_eq!(mid_q2.start_of_quarter, with_deadline.start_of_quarter);
+        assert_eq!(15, with_deadline.days_left_in_quarter);
+        assert!(with_deadline.days_left_in_quarter < mid_q2.days_left_in_quarter);
+        assert!(with_deadline.percent_remaining < mid_q2.percent_remaining);
+
+        let past = NaiveDate::from_ymd_opt(1999, 5, 15).unwrap();
+        assert!(matches!(
+            mid_q2.with_deadline(past),
+            Err(CoordinateError::DeadlineInPast(_))
+        ));
+    }
+
+    #[test]
+    fn test_upcoming_quarters() {
+        let q1 = generate_coordinates(
+            &DateTime::parse_from_rfc3339("1999-02-15T16:39:57+00:00").unwrap(),
+        );
+        let upcoming = q1.upcoming_quarters(4);
+        let labels: Vec<String> = upcoming.iter().map(|q| q.quarter_iso_label()).collect();
+        assert_eq!(vec!["1999-Q2", "1999-Q3", "1999-Q4", "2000-Q1"], labels);
+
+        let days: Vec<i64> = upcoming.iter().map(|q| q1.days_until_start_of(q)).collect();
+        assert_eq!(vec![45, 136, 228, 320], days);
+    }
+
+    #[test]
+    fn test_relative_quarter_rolls_over_fiscal_year() {
+        let t = DateTime::parse_from_rfc3339("1999-11-15T16:39:57+00:00").unwrap();
+        let q1 = generate_fiscal_coordinates(&t, 10);
+        assert_eq!((1, "2000"), (q1.quarter, q1.year.as_str()));
+
+        let previous = q1.relative_quarter(-1);
+        assert_eq!((4, "1999"), (previous.quarter, previous.year.as_str()));
+        assert_eq!(
+            DateTime::parse_from_rfc3339("1999-07-01T00:00:00+00:00").unwrap(),
+            previous.start_of_quarter
+        );
+        assert_eq!(10, previous.fiscal_start_month);
+
+        let later = q1.relative_quarter(4);
+        assert_eq!((1, "2001"), (later.quarter, later.year.as_str()));
+    }
+
+    #[test]
+    fn test_days_to_date() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T23:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(0, coordinates.days_to_date(date(1999, 5, 16)));
+        assert_eq!(46, coordinates.days_to_date(date(1999, 7, 1)));
+        assert_eq!(-15, coordinates.days_to_date(date(1999, 5, 1)));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_generate_coordinates_traces_fields() {
+        use std::io;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<u8>>>);
+
+        impl io::Write for Capture {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        tracing::subscriber::with_default(subscriber, || generate_coordinates(&t));
+
+        let logs = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("DEBUG"));
+        assert!(logs.contains("generate_coordinates"));
+        assert!(logs.contains("quarter=2"));
+        assert!(logs.contains("days_left=45"));
+    }
+
+    #[test]
+    fn test_percentages_at_midnight_starting_quarter() {
+        let t = DateTime::parse_from_rfc3339("1999-04-01T00:00:00+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        assert_eq!(100.0, coordinates.percent_remaining);
+        assert_eq!(0.0, coordinates.percent_elapsed);
+    }
+
+    #[test]
+    fn test_is_last_day_of_quarter() {
+        let at = |time: &str| generate_coordinates(&DateTime::parse_from_rfc3339(time).unwrap());
+        assert!(at("1999-06-30T00:00:00+00:00").is_last_day_of_quarter());
+        assert!(at("1999-06-30T23:59:59+00:00").is_last_day_of_quarter());
+        assert!(!at("1999-06-29T23:59:59+00:00").is_last_day_of_quarter());
+    }
+
+    #[test]
+    fn test_end_of_day_counts_today_as_done() {
+        let at = |time: &str| DateTime::parse_from_rfc3339(time).unwrap();
+        let mid_day = generate_coordinates(&at("1999-05-16T12:00:00+02:00"));
+        let eod = generate_coordinates(&at("1999-05-16T12:00:00+02:00")).as_of_end_of_day();
+
+        assert_eq!(at("1999-05-16T23:59:59+02:00"), eod.generation_time);
+        assert_eq!(45, mid_day.days_remaining_after_today);
+        assert_eq!(45, eod.days_left_in_quarter);
+        assert_eq!(45.0 / 90.0 * 100.0, eod.percent_remaining);
+        assert_eq!(100.0 - eod.percent_remaining, eod.percent_elapsed);
+
+        let second_to_last =
+            generate_coordinates(&at("1999-06-29T12:00:00+02:00")).as_of_end_of_day();
+        assert_eq!(1, second_to_last.days_left_in_quarter);
+        assert_eq!(1, second_to_last.days_remaining_after_today);
+        assert_eq!(1.0 / 90.0 * 100.0, second_to_last.percent_remaining);
+
+        let last_day = generate_coordinates(&at("1999-06-30T12:00:00+02:00")).as_of_end_of_day();
+        assert_eq!(0, last_day.days_left_in_quarter);
+        assert_eq!(100.0, last_day.percent_elapsed);
+    }
+
+    #[test]
+    fn test_percent_remaining_at_agrees_with_generate_coordinates() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+02:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        for instant in [
+            "1999-04-01T00:00:00+02:00",
+            "1999-04-17T08:15:00+02:00",
+            "1999-05-16T23:59:59+02:00",
+            "1999-06-29T12:00:00+02:00",
+        ] {
+            let instant = DateTime::parse_from_rfc3339(instant).unwrap();
+            assert_eq!(
+                generate_coordinates(&instant).percent_remaining,
+                coordinates.percent_remaining_at(instant)
+            );
+        }
+    }
+
+    #[test]
+    fn test_iso_week_quarters_start_on_week_one_monday() {
+        // ISO week 1 of 2026 starts on Monday 29th December 2025.
+        let t = DateTime::parse_from_rfc3339("2026-01-15T16:39:57+00:00").unwrap();
+        let coordinates = generate_iso_week_coordinates(&t);
+        assert_eq!(1, coordinates.quarter);
+        assert_eq!("2026", coordinates.year);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2025-12-29T00:00:00+00:00").unwrap(),
+            coordinates.start_of_quarter
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2026-03-29T00:00:00+00:00").unwrap(),
+            coordinates.end_of_quarter
+        );
+        assert_eq!(13, coordinates.weeks_in_quarter);
+
+        // ...so the 30th December 2025 is already in 2026's first quarter.
+        let t = DateTime::parse_from_rfc3339("2025-12-30T16:39:57+00:00").unwrap();
+        assert_eq!("2026", generate_iso_week_coordinates(&t).year);
+        assert_eq!(1, generate_iso_week_coordinates(&t).quarter);
+    }
+
+    #[test]
+    fn test_iso_week_quarters_lengthen_q4_in_53_week_years() {
+        // 2020 is a 53-week ISO year and week 53 ends on Sunday 3rd January 2021.
+        let t = DateTime::parse_from_rfc3339("2021-01-02T16:39:57+00:00").unwrap();
+        let coordinates = generate_iso_week_coordinates(&t);
+        assert_eq!("2020", coordinates.year);
+        assert_eq!(4, coordinates.quarter);
+        assert_eq!(14, coordinates.weeks_in_quarter);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2021-01-03T00:00:00+00:00").unwrap(),
+            coordinates.end_of_quarter
+        );
+
+        let t = DateTime::parse_from_rfc3339("2019-11-01T16:39:57+00:00").unwrap();
+        assert_eq!(13, generate_iso_week_coordinates(&t).weeks_in_quarter);
+    }
+
+    #[test]
+    fn test_fiscal_coordinates() {
+        let t = DateTime::parse_from_rfc3339("1999-11-15T16:39:57+00:00").unwrap();
+        let coordinates = generate_fiscal_coordinates(&t, 10);
+        assert_eq!(1, coordinates.quarter);
+        assert_eq!("2000", coordinates.year);
+        assert_eq!(10, coordinates.fiscal_start_month);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("1999-10-01T00:00:00+00:00").unwrap(),
+            coordinates.start_of_quarter
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("1999-12-31T00:00:00+00:00").unwrap(),
+            coordinates.end_of_quarter
+        );
+
+        let t = DateTime::parse_from_rfc3339("2000-09-30T16:39:57+00:00").unwrap();
+        let coordinates = generate_fiscal_coordinates(&t, 10);
+        assert_eq!(4, coordinates.quarter);
+        assert_eq!("2000", coordinates.year);
+    }
+
+    #[test]
+    fn test_start_end_quarter() {
+        let q1 = DateTime::parse_from_rfc3339("1999-02-01T16:39:57+00:00").unwrap();
+        let q2 = DateTime::parse_from_rfc3339("1999-05-01T16:39:57+00:00").unwrap();
+        let q3 = DateTime::parse_from_rfc3339("1999-08-01T16:39:57+00:00").unwrap();
+        let q4 = DateTime::parse_from_rfc3339("1999-11-01T16:39:57+00:00").unwrap();
+
+        let start_of_q1 = DateTime::parse_from_rfc3339("1999-01-01T00:00:00+00:00").unwrap();
+        let end_of_q1 = DateTime::parse_from_rfc3339("1999-03-31T00:00:00+00:00").unwrap();
+        assert_eq!(start_of_q1, generate_coordinates(&q1).start_of_quarter);
+        assert_eq!(end_of_q1, generate_coordinates(&q1).end_of_quarter);
+
+        let start_of_q2 = DateTime::parse_from_rfc3339("1999-04-01T00:00:00+00:00").unwrap();
+        let end_of_q2 = DateTime::parse_from_rfc3339("1999-06-30T00:00:00+00:00").unwrap();
+        assert_eq!(start_of_q2, generate_coordinates(&q2).start_of_quarter);
+        assert_eq!(end_of_q2, generate_coordinates(&q2).end_of_quarter);
+
+        let start_of_q3 = DateTime::parse_from_rfc3339("1999-07-01T00:00:00+00:00").unwrap();
+        let end_of_q3 = DateTime::parse_from_rfc3339("1999-09-30T00:00:00+00:00").unwrap();
+        assert_eq!(start_of_q3, generate_coordinates(&q3).start_of_quarter);
+        assert_eq!(end_of_q3, generate_coordinates(&q3).end_of_quarter);
+
+        let start_of_q4 = DateTime::parse_from_rfc3339("1999-10-01T00:00:00+00:00").unwrap();
+        let end_of_q4 = DateTime::parse_from_rfc3339("1999-12-31T00:00:00+00:00").unwrap();
+        assert_eq!(start_of_q4, generate_coordinates(&q4).start_of_quarter);
+        assert_eq!(end_of_q4, generate_coordinates(&q4).end_of_quarter);
+    }
+
+    #[test]
+    fn test_weeks_per_quarter() {
+        assert_eq!(52 / 4, generate_coordinates(&now()).weeks_in_quarter)
+    }
+
+    #[test]
+    fn test_completed_weeks_quarter() {
+        let start_of_year = DateTime::parse_from_rfc3339("1999-01-01T16:39:57+00:00").unwrap();
+        assert_eq!(
+            generate_coordinates(&start_of_year).full_week_of_quarter_done,
+            0
+        );
+
+        let first_week_feb = DateTime::parse_from_rfc3339("1999-02-01T16:39:57+00:00").unwrap();
+        assert_eq!(
+            generate_coordinates(&first_week_feb).full_week_of_quarter_done,
+            4
+        );
+
+        let first_day_q2 = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
+        assert_eq!(
+            generate_coordinates(&first_day_q2).full_week_of_quarter_done,
+            0
+        );
+
+        let last_day_q2 = DateTime::parse_from_rfc3339("1999-06-30T16:39:57+00:00").unwrap();
+        assert_eq!(
+            generate_coordinates(&last_day_q2).full_week_of_quarter_done,
+            12
+        );
+    }
+
+    #[test]
+    fn test_days_left_in_quarter() {
+        let first_day_q2 = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
+        let last_day_q2 = DateTime::parse_from_rfc3339("1999-06-30T16:39:57+00:00").unwrap();
+        assert_eq!(generate_coordinates(&first_day_q2).quarter, 2);
+        assert_eq!(
+            generate_coordinates(&first_day_q2).days_left_in_quarter as i64,
+            last_day_q2.signed_duration_since(first_day_q2).num_days()
+        );
+        assert_eq!(generate_coordinates(&last_day_q2).days_left_in_quarter, 1);
+    }
+
+    #[test]
+    fn test_days_remaining_after_today() {
+        let first_day_q2 = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
+        let last_day_q2 = DateTime::parse_from_rfc3339("1999-06-30T16:39:57+00:00").unwrap();
+        assert_eq!(
+            generate_coordinates(&first_day_q2).days_remaining_after_today,
+            90
+        );
+        assert_eq!(
+            generate_coordinates(&last_day_q2).days_remaining_after_today,
+            0
+        );
+    }
+
+    #[test]
+    fn test_days_in_quarter() {
+        let first_day_q2 = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
+        assert_eq!(generate_coordinates(&first_day_q2).days_in_quarter, 90);
+    }
+
+    #[test]
+    fn test_business_days() {
+        let first_day_q2 = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
+        let last_day_q2 = DateTime::parse_from_rfc3339("1999-06-30T16:39:57+00:00").unwrap();
+        assert_eq!(
+            generate_coordinates(&first_day_q2).total_business_days_in_quarter,
+            65
+        );
+        assert_eq!(
+            generate_coordinates(&first_day_q2).business_days_left_in_quarter,
+            65
+        );
+        assert_eq!(
+            generate_coordinates(&last_day_q2).business_days_left_in_quarter,
+            1
+        );
+
+        let saturday = NaiveDate::from_ymd_opt(1999, 4, 3).unwrap();
+        let sunday = NaiveDate::from_ymd_opt(1999, 4, 4).unwrap();
+        assert_eq!(0, business_days_between(saturday, sunday));
+    }
+
+    #[test]
+    fn test_day_of_quarter() {
+        // 1st April 1999 was a Thursday, so Monday is the 5th calendar day
+        // but only the 3rd business day.
+        let monday = DateTime::parse_from_rfc3339("1999-04-05T16:39:57+00:00").unwrap();
+        assert_eq!(5, generate_coordinates(&monday).day_of_quarter);
+        assert_eq!(3, generate_coordinates(&monday).business_day_of_quarter);
+
+        let sunday = DateTime::parse_from_rfc3339("1999-04-04T16:39:57+00:00").unwrap();
+        assert_eq!(4, generate_coordinates(&sunday).day_of_quarter);
+        assert_eq!(2, generate_coordinates(&sunday).business_day_of_quarter);
+    }
+
+    #[test]
+    fn test_percentages() {
+        let mid_q2 = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&mid_q2);
+        assert_eq!(coordinates.days_left_in_quarter, 45);
+        assert_eq!(coordinates.percent_remaining, 50.0);
+        assert_eq!(coordinates.percent_elapsed, 50.0);
+    }
+}
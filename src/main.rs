@@ -1,241 +1,1242 @@
+use chrono::format::{Item, StrftimeItems};
 use chrono::prelude::*;
-use chrono::{Days, Months};
+use chrono_tz::Tz;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use colored::*;
+use corporateclock::cache::{default_snapshot_path, rollover_message, trend_arrow, Snapshot};
+use corporateclock::compare::{diff_quarter_specs, difference_in_words, QuarterDiff};
+use corporateclock::daycount::DayCount;
+use corporateclock::export::{BadgeBasis, OutputFormat, PercentMode};
+use corporateclock::fiscal::{DayCode, Era, FiscalPreset, FiscalStart, FiscalYearNaming};
+use corporateclock::planning::{
+    days_off_pace, forecast_days_to_finish, full_months_left, health_score, health_verdict,
+    weighted_progress, PhaseWeights,
+};
+use corporateclock::render::{
+    format_days_of_year, format_generation_time, format_percent, format_zone, group_thousands,
+    render_battery, render_fiscal_year_timeline, render_labelled_progress_bar,
+    render_plan_vs_actual, render_quarter_calendar, render_timeline, render_weeks_grid,
+    render_year_bars, to_ascii, BarChars, ElapsedBasis, LabelFormat, PercentPosition, SummaryStyle,
+    UrgencyThresholds,
+};
+use corporateclock::zone::verify_boundary_offsets;
+use corporateclock::*;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::process::{self, Command as ProcessCommand, Stdio};
+use std::{env, fmt};
 
-struct CorporateCoordinates {
-    generation_time: DateTime<FixedOffset>,
-    year: String,
-    quarter: u32,
-    start_of_quarter: DateTime<FixedOffset>,
-    end_of_quarter: DateTime<FixedOffset>,
-    full_week_of_quarter_done: u32,
-    weeks_in_quarter: u32,
-    days_left_in_quarter: u32,
-    days_in_quarter: u32,
-}
-
-fn generate_coordinates(now: &DateTime<FixedOffset>) -> CorporateCoordinates {
-    let quarter = (now.month() as f64 / 3.0).ceil() as u32;
-    let start_of_year = NaiveDate::from_ymd_opt(now.year(), 1, 1)
-        .unwrap()
-        .and_hms_nano_opt(0, 0, 0, 0)
-        .unwrap();
-    let start_of_quarter = now
-        .offset()
-        .from_local_datetime(
-            &start_of_year
-                .checked_add_months(Months::new((quarter - 1) * 3))
-                .unwrap(),
-        )
-        .unwrap();
+const TIMELINE_WIDTH: usize = 60;
+const BAR_WIDTH: usize = 40;
+const ALERT_EXIT_CODE: i32 = 2;
+const ROLLOVER_EXIT_CODE: i32 = 3;
+const BATTERY_WIDTH: usize = 10;
+const LOW_BATTERY_PERCENT: f64 = 20.0;
+const DEFAULT_SPRINT_LENGTH_DAYS: u32 = 14;
 
-    let end_of_quarter = now
-        .offset()
-        .from_local_datetime(
-            &start_of_year
-                .checked_add_months(Months::new((quarter) * 3))
-                .unwrap()
-                .checked_sub_days(Days::new(1))
-                .unwrap(),
-        )
-        .unwrap();
+/// Tells you where you are in the year/quarter.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    CorporateCoordinates {
-        generation_time: now.clone(),
-        year: format!("{}", now.year()),
-        quarter: quarter as u32,
-        start_of_quarter: start_of_quarter,
-        end_of_quarter: end_of_quarter,
-        full_week_of_quarter_done: (now.signed_duration_since(start_of_quarter).num_days() as f64
-            / 7.0)
-            .floor() as u32,
-        weeks_in_quarter: 13,
-        days_left_in_quarter: (end_of_quarter.signed_duration_since(now).num_days() + 1) as u32,
-        days_in_quarter: (end_of_quarter
-            .signed_duration_since(start_of_quarter)
-            .num_days()) as u32,
-    }
+    /// How to print the coordinates
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Add the quarter's risk level to the summary
+    #[arg(long)]
+    verbose: bool,
+
+    /// Print a timeline of the quarter with a marker at today
+    #[arg(long)]
+    timeline: bool,
+
+    /// Print the months of the quarter as calendars, marking today and the
+    /// quarter's last day
+    #[arg(long)]
+    calendar: bool,
+
+    /// Print the weeks of the quarter as a row of cells, marking this week
+    #[arg(long)]
+    weeks_grid: bool,
+
+    /// Print a timeline of the whole fiscal year, ticked at each quarter
+    #[arg(long)]
+    fiscal_year_bar: bool,
+
+    /// Print this message on the last day of the quarter
+    #[arg(long, value_name = "MESSAGE")]
+    quarter_end_message: Option<String>,
+
+    /// Print a table of the quarter's sprints, marking the current one
+    #[arg(long)]
+    sprint_schedule: bool,
+
+    /// Also print the number of days left in the quarter not counting today
+    #[arg(long)]
+    exclude_today: bool,
+
+    /// Also print how many days each 1% of the quarter lasts
+    #[arg(long)]
+    burn_rate: bool,
+
+    /// Also print how many whole calendar months are left in the quarter
+    #[arg(long)]
+    months_left: bool,
+
+    /// Also print the day of the year the quarter starts and ends, and today
+    #[arg(long)]
+    doy: bool,
+
+    /// Print the coordinates in InfluxDB line protocol and exit
+    #[arg(long)]
+    influx: bool,
+
+    /// Print a battery showing how much of the quarter remains
+    #[arg(long)]
+    battery: bool,
+
+    /// Don't color the output
+    #[arg(long)]
+    no_color: bool,
+
+    /// Print a progress bar of the quarter elapsed so far
+    #[arg(long)]
+    bar: bool,
+
+    /// Also print which day of the quarter it is, in calendar or business days
+    #[arg(long, value_enum)]
+    elapsed_basis: Option<ElapsedBasis>,
+
+    /// Print a progress bar for each quarter of the year
+    #[arg(long)]
+    year_bars: bool,
+
+    /// The fill and empty characters to draw progress bars with, e.g. "=-"
+    #[arg(long, value_name = "CHARS")]
+    bar_chars: Option<BarChars>,
+
+    /// Where to put the percentage relative to the progress bar
+    #[arg(long, value_enum, default_value_t)]
+    percent_position: PercentPosition,
+
+    /// Percentage of the quarter's work done, to compare against even pace
+    #[arg(long, value_name = "PERCENT")]
+    target_percent: Option<f64>,
+
+    /// Use this RFC 3339 time, e.g. 2024-05-01T09:00:00+01:00, instead of now
+    #[arg(long, visible_alias = "dry-run-date", value_name = "TIMESTAMP", value_parser = DateTime::parse_from_rfc3339)]
+    date: Option<DateTime<FixedOffset>>,
+
+    /// Report as of the close of business, counting all of today as elapsed
+    #[arg(long)]
+    as_of_eod: bool,
+
+    /// Use this Unix timestamp (seconds since the epoch) instead of now
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        allow_negative_numbers = true,
+        conflicts_with = "date"
+    )]
+    unix_timestamp: Option<i64>,
+
+    /// Read the time to use as now from the RFC 3339 timestamp in this file
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["date", "unix_timestamp"])]
+    now_file: Option<PathBuf>,
+
+    /// Print a shields.io badge URL for the quarter and exit
+    #[arg(long)]
+    badge: bool,
+
+    /// Percent-remaining cutoffs for the yellow, orange and red urgency colors
+    /// of the badge and bar
+    #[arg(long, value_name = "YELLOW,ORANGE,RED", default_value = "50,25,10")]
+    urgency_thresholds: UrgencyThresholds,
+
+    /// Which percentage remaining the badge color is based on
+    #[arg(long, value_enum, default_value_t)]
+    badge_basis: BadgeBasis,
+
+    /// Print the fraction (0-1) of the quarter elapsed and exit
+    #[arg(long)]
+    decimal_fraction: bool,
+
+    /// Print only the whole percentage of the quarter, e.g. 47, and exit
+    #[arg(long)]
+    percent_only: bool,
+
+    /// Whether --percent-only reports the quarter remaining or elapsed
+    #[arg(long, value_enum, default_value_t)]
+    percent_mode: PercentMode,
+
+    /// Print only a compact code for today, e.g. FY24W23D3, and exit
+    #[arg(long, value_enum)]
+    code: Option<DayCode>,
+
+    /// Print a line and exit with status 3 if the quarter has changed since
+    /// the last run; otherwise print nothing. With --silent, only the exit
+    /// status is set
+    #[arg(long, conflicts_with_all = ["date", "unix_timestamp", "now_file"])]
+    detect_rollover: bool,
+
+    /// Print a filename-safe summary, e.g. 2024-Q2_week07_47pct, and exit
+    #[arg(long)]
+    stub: bool,
+
+    /// Print only the (fiscal) year, e.g. 2025, and exit
+    #[arg(long)]
+    year_only: bool,
+
+    /// Print only the quarter number, e.g. 2, and exit
+    #[arg(long)]
+    quarter_only: bool,
+
+    /// Print only the quarter's id, e.g. 2025Q2, and exit
+    #[arg(long)]
+    quarter_id: bool,
+
+    /// How to label the quarter
+    #[arg(long, value_enum, default_value_t)]
+    label_format: LabelFormat,
+
+    /// The number printed for the first quarter of the year, e.g. 0 for
+    /// quarters Q0 to Q3
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    first_quarter_number: u32,
+
+    /// How much the summary says
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        default_value_if("compact", "true", "short")
+    )]
+    summary_style: SummaryStyle,
+
+    /// Print a one-line summary, the same as --summary-style short
+    #[arg(long, conflicts_with = "summary_style")]
+    compact: bool,
+
+    /// Also print when each of the next N quarters starts
+    #[arg(long, value_name = "N")]
+    horizon: Option<usize>,
+
+    /// Project when REMAINING work items will be done at RATE items per day
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["REMAINING", "RATE"],
+        allow_negative_numbers = true
+    )]
+    forecast_end_of_quarter: Option<Vec<f64>>,
+
+    /// Also show progress weighting each month of the quarter, e.g. 1,2,3
+    /// for back-loaded work
+    #[arg(long, value_name = "M1,M2,M3")]
+    phase_weights: Option<PhaseWeights>,
+
+    /// Percentage of the quarter's work completed, to score the quarter's health
+    /// and draw it against the time elapsed
+    #[arg(long, value_name = "PERCENT")]
+    completion: Option<f64>,
+
+    /// Measure progress through the quarter or the month
+    #[arg(long, value_enum, default_value_t)]
+    period: Period,
+
+    /// Use quarters of 13 ISO weeks, starting on the Monday of ISO week 1
+    #[arg(long)]
+    iso_week_quarters: bool,
+
+    /// Exit with status 2 when this percentage of the quarter or less remains
+    #[arg(long, value_name = "PERCENT")]
+    alert_threshold: Option<f64>,
+
+    /// Print nothing to stdout and only set the exit status
+    #[arg(long)]
+    silent: bool,
+
+    /// Pipe the output to this shell command's stdin instead of printing it
+    #[arg(long, value_name = "COMMAND")]
+    pipe_to: Option<String>,
+
+    /// Print every option's effective value and where it came from first
+    #[arg(long)]
+    echo_config: bool,
+
+    /// How to count days within the quarter
+    #[arg(long, value_enum, default_value_t)]
+    day_count: DayCount,
+
+    /// Format numbers for this locale, e.g. de for 47,30%
+    #[arg(long)]
+    locale: Option<String>,
+
+    /// Report the time in this IANA time zone, e.g. America/Los_Angeles
+    #[arg(long, value_name = "TZ")]
+    timezone: Option<Tz>,
+
+    /// Check the quarter's boundaries have the offsets this time zone gives
+    /// them, then exit
+    #[arg(long, value_name = "TZ")]
+    test_timezone: Option<Tz>,
+
+    /// Show the time zone abbreviation and offset after the time
+    #[arg(long)]
+    show_zone: bool,
+
+    /// Report on the quarter N quarters before (negative) or after this one
+    #[arg(
+        long,
+        value_name = "N",
+        allow_negative_numbers = true,
+        conflicts_with = "iso_week_quarters"
+    )]
+    relative_quarter: Option<i32>,
+
+    /// Use the fiscal year of a well-known calendar
+    #[arg(long, value_enum, conflicts_with = "iso_week_quarters")]
+    fiscal_preset: Option<FiscalPreset>,
+
+    /// Use a fiscal year starting in this month (1-12)
+    #[arg(
+        long,
+        value_name = "MONTH",
+        value_parser = clap::value_parser!(u32).range(1..=12),
+        conflicts_with_all = ["iso_week_quarters", "fiscal_preset"]
+    )]
+    fiscal_year_start: Option<u32>,
+
+    /// Print a summary for each of several labelled fiscal calendars, e.g.
+    /// --fiscal-start Parent=1 --fiscal-start Subsidiary=10
+    #[arg(long, value_name = "LABEL=MONTH", conflicts_with = "iso_week_quarters")]
+    fiscal_start: Vec<FiscalStart>,
+
+    /// How to write years in the summary
+    #[arg(long, value_enum, default_value_t)]
+    era: Era,
+
+    /// Show times on a 12-hour clock with AM/PM
+    #[arg(long)]
+    twelve_hour: bool,
+
+    /// A strftime format for showing times, overriding --twelve-hour
+    #[arg(long, value_name = "FORMAT")]
+    time_format: Option<String>,
+
+    /// Show whether the percentage remaining has gone up or down since the
+    /// last run with --trend (ignored with --period month, --date,
+    /// --unix-timestamp or --now-file, which would overwrite the last run)
+    #[arg(long)]
+    trend: bool,
+
+    /// Count the time remaining up to this date (YYYY-MM-DD) instead of the
+    /// end of the quarter
+    #[arg(long, value_name = "DATE")]
+    deadline: Option<NaiveDate>,
+
+    /// Only use ASCII in bars and timelines (the default when the terminal
+    /// isn't known to support UTF-8)
+    #[arg(long)]
+    ascii: bool,
+
+    /// With --format json, print every quarter of the year as a JSON array
+    #[cfg(feature = "json")]
+    #[arg(long)]
+    all_quarters: bool,
+
+    /// The year --all-quarters reports on, instead of the current one
+    #[cfg(feature = "json")]
+    #[arg(long, requires = "all_quarters")]
+    year: Option<i32>,
+
+    /// Print the coordinates as YAML and exit
+    #[cfg(feature = "yaml")]
+    #[arg(long)]
+    yaml: bool,
+
+    /// Print the coordinates as RON and exit
+    #[cfg(feature = "ron")]
+    #[arg(long)]
+    ron: bool,
+
+    /// Also copy the plain summary to the clipboard
+    #[cfg(feature = "clipboard")]
+    #[arg(long)]
+    clipboard: bool,
+
+    /// Post the summary to this Slack channel using SLACK_API_TOKEN and exit
+    #[cfg(feature = "slack")]
+    #[arg(long, value_name = "CHANNEL")]
+    notify_slack_channel: Option<String>,
 }
 
-fn local_to_fixed(local_date_time: &DateTime<Local>) -> DateTime<FixedOffset> {
-    local_date_time.with_timezone(local_date_time.offset())
+#[derive(Subcommand)]
+enum Command {
+    /// Compare the lengths and start days of two quarters, e.g. Q2-2024 Q2-2025
+    DiffQuarters { from: String, to: String },
+    /// Run a command with the coordinates set as CC_* environment variables
+    Exec {
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
 }
 
-fn print_summary(coordinates: &CorporateCoordinates) {
-    println!(
+fn quarter_label(coordinates: &CorporateCoordinates, cli: &Cli) -> String {
+    let year = match (cli.era, coordinates.japanese_era_label()) {
+        (Era::Japanese, Some(year)) => year,
+        _ => coordinates.year.clone(),
+    };
+    cli.label_format.format(
+        coordinates.quarter_number_from(cli.first_quarter_number),
+        &year,
+    )
+}
+
+fn summary(coordinates: &CorporateCoordinates, cli: &Cli, trend: Option<&str>) -> String {
+    let mut summary = String::new();
+    let percent_remaining = match trend {
+        Some(arrow) => format!(
+            "{} {}",
+            format_percent(coordinates.percent_remaining, cli.locale.as_deref()),
+            arrow
+        ),
+        None => format_percent(coordinates.percent_remaining, cli.locale.as_deref()),
+    };
+    let mut time = format_generation_time(
+        &coordinates.generation_time,
+        cli.time_format.as_deref(),
+        cli.twelve_hour,
+    );
+    if cli.show_zone {
+        time = format!(
+            "{} {}",
+            time,
+            format_zone(&coordinates.generation_time, cli.timezone)
+        );
+    }
+    if cli.summary_style == SummaryStyle::Short {
+        write!(
+            summary,
+            "{}: {} remaining, {} days left{}",
+            quarter_label(coordinates, cli).red().bold(),
+            percent_remaining.red().bold(),
+            group_thousands(coordinates.days_left_in_quarter.into())
+                .red()
+                .bold(),
+            if cli.deadline.is_some() {
+                " before the deadline"
+            } else {
+                ""
+            }
+        )
+        .unwrap();
+        if cli.show_zone {
+            write!(summary, ", as of {}", time.red().bold()).unwrap();
+        }
+        summary.push('.');
+        if cli.verbose {
+            write!(
+                summary,
+                " The quarter is {}.",
+                coordinates.risk_level().to_string().red().bold()
+            )
+            .unwrap();
+        }
+        summary.push('\n');
+        return summary;
+    }
+    writeln!(
+        summary,
         "We are {} into {}.",
         format!("{} weeks", coordinates.full_week_of_quarter_done)
             .red()
             .bold(),
-        format!("Q{}, {}", coordinates.quarter, coordinates.year)
-            .red()
-            .bold()
-    );
-    println!(
-        "The quarter started {} and will end {} (each quarter is {} weeks).",
+        quarter_label(coordinates, cli).red().bold()
+    )
+    .unwrap();
+    writeln!(
+        summary,
+        "The quarter started {} and {} {} (each quarter is {} weeks).",
         format!("{}", coordinates.start_of_quarter.format("%A, %d %B"))
             .red()
             .bold(),
+        if cli.deadline.is_some() {
+            "the deadline is"
+        } else {
+            "will end"
+        },
         format!("{}", coordinates.end_of_quarter.format("%A, %d %B"))
             .red()
             .bold(),
         format!("{}", coordinates.weeks_in_quarter).red().bold()
-    );
-    println!(
-        "There is {} of the quarter remaining ({} calendar days).",
-        format!(
-            "{:.2}%",
-            (coordinates.days_left_in_quarter as f64 / coordinates.days_in_quarter as f64) * 100.0
+    )
+    .unwrap();
+    writeln!(
+        summary,
+        "There is {} of the quarter remaining ({} {} days).",
+        percent_remaining.red().bold(),
+        group_thousands(coordinates.days_left_in_quarter.into())
+            .red()
+            .bold(),
+        match cli.day_count {
+            DayCount::Actual => "calendar",
+            DayCount::Thirty360 => "30/360",
+        }
+    )
+    .unwrap();
+    writeln!(summary, "The time and date now is {}.", time.red().bold()).unwrap();
+    if cli.verbose {
+        writeln!(
+            summary,
+            "The quarter is {}.",
+            coordinates.risk_level().to_string().red().bold()
         )
-        .red()
-        .bold(),
-        format!("{}", (coordinates.days_left_in_quarter))
+        .unwrap();
+    }
+    summary
+}
+
+fn month_summary(coordinates: &CorporateCoordinates) -> String {
+    format!(
+        "Day {} of {} in {}, {} remaining.\n",
+        coordinates.day_of_quarter,
+        coordinates.day_of_quarter + coordinates.days_remaining_after_today,
+        coordinates.start_of_quarter.format("%B"),
+        format!("{:.0}%", coordinates.percent_remaining)
             .red()
             .bold()
+    )
+}
+
+fn print_quarter_diff(diff: &QuarterDiff) {
+    println!(
+        "{} compared to {}:",
+        diff.to.quarter_iso_label().red().bold(),
+        diff.from.quarter_iso_label().red().bold()
     );
+    let (days, length) = difference_in_words(diff.days, ("longer", "shorter"));
+    let (business_days, count) = difference_in_words(diff.business_days, ("more", "fewer"));
+    let (weekday_days, direction) =
+        difference_in_words(diff.start_weekday_days, ("later", "earlier"));
     println!(
-        "The time and date now is {}.",
-        format!("{}", coordinates.generation_time.format("%+"))
+        "It is {} {}, with {} {} business days.",
+        format!("{} days", days).red().bold(),
+        length,
+        format!("{}", business_days).red().bold(),
+        count
+    );
+    println!(
+        "It starts on a {} rather than a {} ({} {} in the week).",
+        format!("{}", diff.to.start_of_quarter.format("%A"))
             .red()
-            .bold()
+            .bold(),
+        format!("{}", diff.from.start_of_quarter.format("%A"))
+            .red()
+            .bold(),
+        format!("{} days", weekday_days).red().bold(),
+        direction
     );
 }
 
-fn main() {
-    let coordinates = generate_coordinates(&local_to_fixed(&Local::now()));
-    print_summary(&coordinates);
+fn print_forecast(coordinates: &CorporateCoordinates, remaining_items: f64, daily_rate: f64) {
+    let days = forecast_days_to_finish(remaining_items, daily_rate)
+        .expect("the forecast rate is checked before anything is printed");
+    let slack = coordinates.days_left_in_quarter as f64 - days;
+    println!(
+        "At {} items/day, you will finish in {}. The quarter ends in {} — you are {} schedule by {}.",
+        daily_rate,
+        format!("{:.1} days", days).red().bold(),
+        format!("{} days", coordinates.days_left_in_quarter)
+            .red()
+            .bold(),
+        if slack < 0.0 { "behind" } else { "ahead of" },
+        format!("{:.1} days", slack.abs()).red().bold()
+    );
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn print_pace(coordinates: &CorporateCoordinates, target_percent: f64) {
+    let days = days_off_pace(
+        coordinates.percent_elapsed / 100.0,
+        target_percent / 100.0,
+        coordinates.days_in_quarter,
+    );
+    println!(
+        "You are {} {} even pace.",
+        format!("{:.1} days", days.abs()).red().bold(),
+        if days < 0.0 { "behind" } else { "ahead of" }
+    );
+}
 
-    fn now() -> DateTime<FixedOffset> {
-        local_to_fixed(&Local::now())
+fn print_sprint_schedule(coordinates: &CorporateCoordinates, visual: impl Fn(String) -> String) {
+    let today = coordinates.generation_time.date_naive();
+    let sprints = coordinates.sprint_schedule(
+        coordinates
+            .sprint_length_days
+            .unwrap_or(DEFAULT_SPRINT_LENGTH_DAYS),
+        Weekday::Mon,
+        &[],
+    );
+    println!(
+        "  {:>2}  {:<10} {:<10} {:<10} status",
+        "#", "name", "start", "end"
+    );
+    for sprint in sprints {
+        let (marker, status) = if sprint.is_current {
+            (visual("→".to_string()), "current")
+        } else if sprint.end < today {
+            (" ".to_string(), "complete")
+        } else {
+            (" ".to_string(), "upcoming")
+        };
+        let row = format!(
+            "{} {:>2}  {:<10} {:<10} {:<10} {}",
+            marker, sprint.number, sprint.name, sprint.start, sprint.end, status
+        );
+        match status {
+            "current" => println!("{}", row.red().bold()),
+            "complete" => println!("{}", row.dimmed()),
+            _ => println!("{}", row),
+        }
     }
+}
 
-    #[test]
-    fn test_generation_time() {
-        let t = now();
-        assert_eq!(t, generate_coordinates(&t).generation_time)
+fn utf8_output_supported() -> bool {
+    if cfg!(windows) {
+        // Windows Terminal is UTF-8; the legacy console depends on the code page.
+        return env::var_os("WT_SESSION").is_some();
     }
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()))
+        .map(|locale| {
+            let locale = locale.to_uppercase();
+            locale.contains("UTF-8") || locale.contains("UTF8")
+        })
+        .unwrap_or(false)
+}
 
-    #[test]
-    fn test_year_correct() {
-        let t = now();
-        assert_eq!(format!("{}", t.year()), generate_coordinates(&t).year)
+/// Names the calendar the coordinates were generated in, for snapshots.
+fn calendar_id(coordinates: &CorporateCoordinates, cli: &Cli) -> String {
+    match (cli.period, cli.iso_week_quarters) {
+        (Period::Month, _) => "months".to_string(),
+        (Period::Quarter, true) => "iso-weeks".to_string(),
+        (Period::Quarter, false) => format!(
+            "fiscal-{}-{}",
+            coordinates.fiscal_start_month,
+            match coordinates.fiscal_year_naming() {
+                FiscalYearNaming::EndYear => "end-year",
+                FiscalYearNaming::StartYear => "start-year",
+            }
+        ),
     }
+}
 
-    #[test]
-    fn test_quarters_correct() {
-        let q1_jan = DateTime::parse_from_rfc3339("1999-01-01T16:39:57+00:00").unwrap();
-        let q1_feb = DateTime::parse_from_rfc3339("1999-02-01T16:39:57+00:00").unwrap();
-        let q1_mar = DateTime::parse_from_rfc3339("1999-03-01T16:39:57+00:00").unwrap();
-
-        let q2_apr = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
-        let q2_may = DateTime::parse_from_rfc3339("1999-05-01T16:39:57+00:00").unwrap();
-        let q2_jun = DateTime::parse_from_rfc3339("1999-06-01T16:39:57+00:00").unwrap();
-
-        let q3_jul = DateTime::parse_from_rfc3339("1999-07-01T16:39:57+00:00").unwrap();
-        let q3_aug = DateTime::parse_from_rfc3339("1999-08-01T16:39:57+00:00").unwrap();
-        let q3_sep = DateTime::parse_from_rfc3339("1999-09-01T16:39:57+00:00").unwrap();
-
-        let q4_oct = DateTime::parse_from_rfc3339("1999-10-01T16:39:57+00:00").unwrap();
-        let q4_nov = DateTime::parse_from_rfc3339("1999-11-01T16:39:57+00:00").unwrap();
-        let q4_dec = DateTime::parse_from_rfc3339("1999-12-01T16:39:57+00:00").unwrap();
+/// Compares against the last run's snapshot and records this run's.
+/// Replaces the previous run's snapshot with this one's, returning the
+/// previous one if it was taken in the same calendar.
+fn swap_snapshot(coordinates: &CorporateCoordinates, cli: &Cli) -> Option<Snapshot> {
+    let path = default_snapshot_path()?;
+    let calendar = calendar_id(coordinates, cli);
+    let previous = Snapshot::load(&path, &calendar);
+    if let Err(e) = Snapshot::of(coordinates, &calendar).save(&path) {
+        eprintln!("warning: could not save {}: {}", path.display(), e);
+    }
+    previous
+}
 
-        assert_eq!(1, generate_coordinates(&q1_jan).quarter);
-        assert_eq!(1, generate_coordinates(&q1_feb).quarter);
-        assert_eq!(1, generate_coordinates(&q1_mar).quarter);
+fn record_trend(coordinates: &CorporateCoordinates, cli: &Cli) -> Option<char> {
+    trend_arrow(
+        swap_snapshot(coordinates, cli).map(|snapshot| snapshot.percent_remaining),
+        coordinates.percent_remaining,
+    )
+}
 
-        assert_eq!(2, generate_coordinates(&q2_apr).quarter);
-        assert_eq!(2, generate_coordinates(&q2_may).quarter);
-        assert_eq!(2, generate_coordinates(&q2_jun).quarter);
+fn command_with_env(command: &[String], coordinates: &CorporateCoordinates) -> ProcessCommand {
+    let mut process = ProcessCommand::new(&command[0]);
+    process.args(&command[1..]).envs(coordinates.to_env_vars());
+    process
+}
 
-        assert_eq!(3, generate_coordinates(&q3_jul).quarter);
-        assert_eq!(3, generate_coordinates(&q3_aug).quarter);
-        assert_eq!(3, generate_coordinates(&q3_sep).quarter);
+fn test_timezone(coordinates: &CorporateCoordinates, zone: Tz) {
+    let now = coordinates
+        .generation_time
+        .with_timezone(&zone)
+        .fixed_offset();
+    let coordinates = generate_fiscal_coordinates_with_naming(
+        &now,
+        coordinates.fiscal_start_month,
+        coordinates.fiscal_year_naming(),
+    );
+    let mismatches = verify_boundary_offsets(&coordinates, zone);
+    for mismatch in &mismatches {
+        println!(
+            "{} is {}, but {} has offset {} then.",
+            mismatch.boundary,
+            mismatch.time.to_rfc3339(),
+            zone,
+            mismatch.expected
+        );
+    }
+    if !mismatches.is_empty() {
+        process::exit(1);
+    }
+    println!("Quarter boundaries match {}.", zone);
+}
 
-        assert_eq!(4, generate_coordinates(&q4_oct).quarter);
-        assert_eq!(4, generate_coordinates(&q4_nov).quarter);
-        assert_eq!(4, generate_coordinates(&q4_dec).quarter);
+/// Re-runs this program without `--pipe-to`, piping its output into `command`
+/// run by the shell, and exits with the command's status.
+fn pipe_to(command: &str) -> ! {
+    let mut args = env::args_os().skip(1);
+    let mut forwarded = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--pipe-to" {
+            args.next();
+        } else if !arg.to_string_lossy().starts_with("--pipe-to=") {
+            forwarded.push(arg);
+        }
     }
+    let exe = env::current_exe().unwrap_or_else(|e| exit_with_error(e));
+    let mut producer = ProcessCommand::new(exe)
+        .args(forwarded)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| exit_with_error(e));
+    let mut consumer = if cfg!(windows) {
+        let mut consumer = ProcessCommand::new("cmd");
+        consumer.arg("/C");
+        consumer
+    } else {
+        let mut consumer = ProcessCommand::new("sh");
+        consumer.arg("-c");
+        consumer
+    };
+    let status = consumer
+        .arg(command)
+        .stdin(producer.stdout.take().unwrap())
+        .status()
+        .unwrap_or_else(|e| exit_with_error(format!("could not run {}: {}", command, e)));
+    let produced = producer.wait().unwrap_or_else(|e| exit_with_error(e));
+    let status = if status.success() { produced } else { status };
+    process::exit(status.code().unwrap_or(1));
+}
 
-    #[test]
-    fn test_start_end_quarter() {
-        let q1 = DateTime::parse_from_rfc3339("1999-02-01T16:39:57+00:00").unwrap();
-        let q2 = DateTime::parse_from_rfc3339("1999-05-01T16:39:57+00:00").unwrap();
-        let q3 = DateTime::parse_from_rfc3339("1999-08-01T16:39:57+00:00").unwrap();
-        let q4 = DateTime::parse_from_rfc3339("1999-11-01T16:39:57+00:00").unwrap();
+fn urgency_colored(text: String, color: &str) -> ColoredString {
+    match color {
+        "green" => text.green(),
+        "yellow" => text.yellow(),
+        "orange" => text.truecolor(255, 165, 0),
+        _ => text.red(),
+    }
+}
 
-        let start_of_q1 = DateTime::parse_from_rfc3339("1999-01-01T00:00:00+00:00").unwrap();
-        let end_of_q1 = DateTime::parse_from_rfc3339("1999-03-31T00:00:00+00:00").unwrap();
-        assert_eq!(start_of_q1, generate_coordinates(&q1).start_of_quarter);
-        assert_eq!(end_of_q1, generate_coordinates(&q1).end_of_quarter);
+fn exit_with_error(e: impl fmt::Display) -> ! {
+    eprintln!("error: {}", e);
+    process::exit(1);
+}
 
-        let start_of_q2 = DateTime::parse_from_rfc3339("1999-04-01T00:00:00+00:00").unwrap();
-        let end_of_q2 = DateTime::parse_from_rfc3339("1999-06-30T00:00:00+00:00").unwrap();
-        assert_eq!(start_of_q2, generate_coordinates(&q2).start_of_quarter);
-        assert_eq!(end_of_q2, generate_coordinates(&q2).end_of_quarter);
+/// Whether the coordinates are for the current time rather than one given on
+/// the command line.
+fn is_now(cli: &Cli) -> bool {
+    cli.date.is_none() && cli.unix_timestamp.is_none() && cli.now_file.is_none()
+}
 
-        let start_of_q3 = DateTime::parse_from_rfc3339("1999-07-01T00:00:00+00:00").unwrap();
-        let end_of_q3 = DateTime::parse_from_rfc3339("1999-09-30T00:00:00+00:00").unwrap();
-        assert_eq!(start_of_q3, generate_coordinates(&q3).start_of_quarter);
-        assert_eq!(end_of_q3, generate_coordinates(&q3).end_of_quarter);
+fn coordinates(cli: &Cli) -> Result<CorporateCoordinates, CoordinateError> {
+    let now = match (cli.date, cli.unix_timestamp, &cli.now_file) {
+        (Some(date), _, _) => date,
+        (None, Some(timestamp), _) => CorporateCoordinates::try_from(timestamp)?.generation_time,
+        (None, None, Some(path)) => read_now_file(path)?,
+        (None, None, None) => local_to_fixed(&Local::now()),
+    };
+    let now = match cli.timezone {
+        Some(zone) => now.with_timezone(&zone).fixed_offset(),
+        None => now,
+    };
+    let fiscal_start_month = cli
+        .fiscal_preset
+        .map(|preset| preset.start_month())
+        .or(cli.fiscal_year_start);
+    let coordinates = match (cli.period, cli.iso_week_quarters, fiscal_start_month) {
+        (Period::Month, _, _) => generate_month_coordinates(&now),
+        (Period::Quarter, true, _) => generate_iso_week_coordinates(&now),
+        (Period::Quarter, false, Some(month)) => generate_fiscal_coordinates_with_naming(
+            &now,
+            month,
+            cli.fiscal_preset
+                .map(|preset| preset.year_naming())
+                .unwrap_or_default(),
+        ),
+        (Period::Quarter, false, None) => generate_coordinates(&now),
+    };
+    let coordinates = match cli.relative_quarter {
+        Some(offset) => coordinates.relative_quarter(offset),
+        None => coordinates,
+    };
+    let coordinates = match cli.deadline {
+        Some(deadline) => coordinates.with_deadline(deadline)?,
+        None => coordinates,
+    };
+    let coordinates = coordinates.with_day_count(cli.day_count);
+    Ok(if cli.as_of_eod {
+        coordinates.as_of_end_of_day()
+    } else {
+        coordinates
+    })
+}
 
-        let start_of_q4 = DateTime::parse_from_rfc3339("1999-10-01T00:00:00+00:00").unwrap();
-        let end_of_q4 = DateTime::parse_from_rfc3339("1999-12-31T00:00:00+00:00").unwrap();
-        assert_eq!(start_of_q4, generate_coordinates(&q4).start_of_quarter);
-        assert_eq!(end_of_q4, generate_coordinates(&q4).end_of_quarter);
+fn print_config(matches: &ArgMatches) {
+    println!("{:<28} {:<32} source", "option", "value");
+    for arg in Cli::command().get_arguments() {
+        let id = arg.get_id().as_str();
+        if id == "help" || id == "version" {
+            continue;
+        }
+        let values: Vec<_> = matches
+            .get_raw(id)
+            .map(|values| values.collect())
+            .unwrap_or_default();
+        let value = if values.is_empty() {
+            "-".to_string()
+        } else {
+            values
+                .iter()
+                .map(|value| value.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        // Defaults that other options override, like --compact's summary
+        // style, come from those options rather than the default.
+        let source = match matches.value_source(id) {
+            Some(ValueSource::CommandLine) => "CLI",
+            _ if !arg.get_default_values().is_empty() && values != arg.get_default_values() => {
+                "implied"
+            }
+            _ => "default",
+        };
+        let name = arg
+            .get_long()
+            .map_or_else(|| id.to_string(), |long| format!("--{}", long));
+        println!("{:<28} {:<32} {}", name, value, source);
     }
+    println!();
+}
 
-    #[test]
-    fn test_weeks_per_quarter() {
-        assert_eq!(52 / 4, generate_coordinates(&now()).weeks_in_quarter)
+fn main() {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if cli.no_color {
+        colored::control::set_override(false);
     }
-
-    #[test]
-    fn test_completed_weeks_quarter() {
-        let start_of_year = DateTime::parse_from_rfc3339("1999-01-01T16:39:57+00:00").unwrap();
-        assert_eq!(
-            generate_coordinates(&start_of_year).full_week_of_quarter_done,
-            0
+    if let Some(command) = &cli.pipe_to {
+        pipe_to(command);
+    }
+    if cli.echo_config {
+        print_config(&matches);
+    }
+    if let Some(time_format) = &cli.time_format {
+        if StrftimeItems::new(time_format).any(|item| item == Item::Error) {
+            exit_with_error(format!("{} is not a valid --time-format", time_format));
+        }
+    }
+    if cli.relative_quarter.is_some() && cli.period == Period::Month {
+        exit_with_error(
+            "--relative-quarter reports on a quarter, so it can't be used with --period month",
         );
-
-        let first_week_feb = DateTime::parse_from_rfc3339("1999-02-01T16:39:57+00:00").unwrap();
-        assert_eq!(
-            generate_coordinates(&first_week_feb).full_week_of_quarter_done,
-            4
+    }
+    if let Some(forecast) = &cli.forecast_end_of_quarter {
+        if forecast_days_to_finish(forecast[0], forecast[1]).is_none() {
+            exit_with_error("the forecast rate must be more than zero");
+        }
+    }
+    let coordinates = coordinates(&cli).unwrap_or_else(|e| exit_with_error(e));
+    if let Some(zone) = cli.test_timezone {
+        test_timezone(&coordinates, zone);
+        return;
+    }
+    match &cli.command {
+        Some(Command::DiffQuarters { from, to }) => {
+            let diff = diff_quarter_specs(from, to, *coordinates.generation_time.offset())
+                .unwrap_or_else(|e| exit_with_error(e));
+            print_quarter_diff(&diff);
+            return;
+        }
+        Some(Command::Exec { command }) => {
+            let status = command_with_env(command, &coordinates)
+                .status()
+                .unwrap_or_else(|e| {
+                    exit_with_error(format!("could not run {}: {}", command[0], e))
+                });
+            process::exit(status.code().unwrap_or(1));
+        }
+        None => {}
+    }
+    if cli.detect_rollover {
+        let previous = swap_snapshot(&coordinates, &cli);
+        if let Some(message) = previous.and_then(|p| rollover_message(&p, &coordinates)) {
+            if !cli.silent {
+                println!("{}", message);
+            }
+            process::exit(ROLLOVER_EXIT_CODE);
+        }
+        if !cli.silent {
+            return;
+        }
+    }
+    let alert = cli
+        .alert_threshold
+        .is_some_and(|threshold| coordinates.percent_remaining <= threshold);
+    if cli.silent {
+        process::exit(if alert { ALERT_EXIT_CODE } else { 0 });
+    }
+    let ascii = cli.ascii || !utf8_output_supported();
+    let visual = |rendered: String| if ascii { to_ascii(&rendered) } else { rendered };
+    match cli.format {
+        OutputFormat::Text => {}
+        OutputFormat::Notify => {
+            println!("{}", coordinates.to_notify_payload());
+            return;
+        }
+        OutputFormat::Env => {
+            for (name, value) in coordinates.to_env_vars() {
+                println!("{}={}", name, value);
+            }
+            return;
+        }
+        OutputFormat::Org => {
+            print!("{}", coordinates.to_org());
+            return;
+        }
+        #[cfg(feature = "json")]
+        OutputFormat::Json => {
+            if cli.all_quarters {
+                let year = cli
+                    .year
+                    .unwrap_or_else(|| coordinates.generation_time.year());
+                let quarters =
+                    generate_year_coordinates(year, *coordinates.generation_time.offset())
+                        .unwrap_or_else(|e| exit_with_error(e));
+                println!("{}", corporateclock::export::to_json_array(&quarters));
+            } else {
+                println!("{}", coordinates.to_json());
+            }
+            return;
+        }
+        #[cfg(feature = "json")]
+        OutputFormat::JsonBase64 => {
+            println!("{}", coordinates.to_json_base64());
+            return;
+        }
+    }
+    if let Some(code) = cli.code {
+        println!("{}", coordinates.day_code(code));
+        return;
+    }
+    if cli.stub {
+        println!("{}", coordinates.to_filename_stub());
+        return;
+    }
+    if cli.year_only {
+        println!("{}", coordinates.year);
+        return;
+    }
+    if cli.quarter_only {
+        println!(
+            "{}",
+            coordinates.quarter_number_from(cli.first_quarter_number)
         );
-
-        let first_day_q2 = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
-        assert_eq!(
-            generate_coordinates(&first_day_q2).full_week_of_quarter_done,
-            0
+        return;
+    }
+    if cli.quarter_id {
+        println!("{}", coordinates.year_quarter_id());
+        return;
+    }
+    if cli.percent_only {
+        println!("{}", coordinates.to_percent_only(cli.percent_mode));
+        return;
+    }
+    if cli.decimal_fraction {
+        println!("{}", coordinates.quarter_fraction_elapsed_as_decimal());
+        return;
+    }
+    if cli.badge {
+        println!(
+            "{}",
+            coordinates.to_shields_badge_url(cli.badge_basis, &cli.urgency_thresholds)
         );
-
-        let last_day_q2 = DateTime::parse_from_rfc3339("1999-06-30T16:39:57+00:00").unwrap();
-        assert_eq!(
-            generate_coordinates(&last_day_q2).full_week_of_quarter_done,
-            12
+        return;
+    }
+    #[cfg(feature = "yaml")]
+    if cli.yaml {
+        print!("{}", coordinates.to_yaml());
+        return;
+    }
+    #[cfg(feature = "ron")]
+    if cli.ron {
+        println!("{}", coordinates.to_ron());
+        return;
+    }
+    #[cfg(feature = "slack")]
+    if let Some(channel) = &cli.notify_slack_channel {
+        let token = env::var("SLACK_API_TOKEN").unwrap_or_else(|_| {
+            exit_with_error("--notify-slack-channel needs SLACK_API_TOKEN to be set")
+        });
+        corporateclock::slack::post_to_channel(&coordinates, channel, &token)
+            .unwrap_or_else(|e| exit_with_error(e));
+        return;
+    }
+    if cli.influx {
+        println!(
+            "{}",
+            coordinates
+                .to_influx_line_protocol()
+                .unwrap_or_else(|e| exit_with_error(e))
         );
+        return;
     }
-
-    #[test]
-    fn test_days_left_in_quarter() {
-        let first_day_q2 = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
-        let last_day_q2 = DateTime::parse_from_rfc3339("1999-06-30T16:39:57+00:00").unwrap();
-        assert_eq!(generate_coordinates(&first_day_q2).quarter, 2);
-        assert_eq!(
-            generate_coordinates(&first_day_q2).days_left_in_quarter as i64,
-            last_day_q2.signed_duration_since(first_day_q2).num_days()
+    if !cli.fiscal_start.is_empty() {
+        for (i, calendar) in cli.fiscal_start.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            println!("{}", format!("{}:", calendar.label).bold());
+            print!(
+                "{}",
+                summary(
+                    &calendar.coordinates(&coordinates.generation_time),
+                    &cli,
+                    None,
+                )
+            );
+        }
+        return;
+    }
+    // Only swap the snapshot when the summary will show the arrow.
+    let trend = if cli.trend && is_now(&cli) && cli.period == Period::Quarter {
+        record_trend(&coordinates, &cli).map(|arrow| visual(arrow.to_string()))
+    } else {
+        None
+    };
+    let mut summary = match cli.period {
+        Period::Quarter => summary(&coordinates, &cli, trend.as_deref()),
+        Period::Month => month_summary(&coordinates),
+    };
+    if let Some(basis) = cli.elapsed_basis {
+        writeln!(
+            summary,
+            "{}.",
+            basis.day_of_quarter(&coordinates).red().bold()
+        )
+        .unwrap();
+    }
+    print!("{}", summary);
+    #[cfg(feature = "clipboard")]
+    if cli.clipboard {
+        let plain = corporateclock::render::strip_colors(&summary);
+        if let Err(e) = corporateclock::clipboard::copy_text(&plain) {
+            eprintln!(
+                "note: the summary wasn't copied, no clipboard is available: {}",
+                e
+            );
+        }
+    }
+    if cli.exclude_today {
+        println!(
+            "Not counting today, there are {} left in the quarter.",
+            format!(
+                "{} days",
+                group_thousands(coordinates.days_remaining_after_today.into())
+            )
+            .red()
+            .bold()
+        );
+    }
+    if cli.burn_rate {
+        println!(
+            "Each 1% of the quarter is {}.",
+            format!("{:.2} days", coordinates.days_per_percent())
+                .red()
+                .bold()
+        );
+    }
+    if cli.months_left {
+        println!(
+            "{} remain in the quarter.",
+            format!(
+                "{} full calendar months",
+                full_months_left(&coordinates.generation_time, &coordinates.end_of_quarter)
+            )
+            .red()
+            .bold()
         );
-        assert_eq!(generate_coordinates(&last_day_q2).days_left_in_quarter, 1);
     }
+    if cli.doy {
+        println!(
+            "Day of the year: {}.",
+            format_days_of_year(&coordinates).red().bold()
+        );
+    }
+    if let Some(target_percent) = cli.target_percent {
+        print_pace(&coordinates, target_percent);
+    }
+    if let Some(forecast) = &cli.forecast_end_of_quarter {
+        print_forecast(&coordinates, forecast[0], forecast[1]);
+    }
+    if let Some(PhaseWeights(weights)) = cli.phase_weights {
+        let progress = weighted_progress(
+            &coordinates.generation_time,
+            (coordinates.start_of_quarter, coordinates.end_of_quarter),
+            weights,
+        );
+        println!(
+            "Weighted progress is {} (calendar progress is {}).",
+            format!("{:.2}%", progress).red().bold(),
+            format!("{:.2}%", coordinates.percent_elapsed).red().bold()
+        );
+    }
+    if let Some(completion) = cli.completion {
+        let score = health_score(coordinates.percent_elapsed / 100.0, completion / 100.0);
+        println!(
+            "Quarter health is {}/100: {}.",
+            format!("{}", score).red().bold(),
+            health_verdict(score).red().bold()
+        );
+        let [time, work] = render_plan_vs_actual(
+            coordinates.percent_elapsed / 100.0,
+            completion / 100.0,
+            BAR_WIDTH,
+            cli.bar_chars.unwrap_or_default(),
+        );
+        println!("{}", visual(time));
+        if completion < coordinates.percent_elapsed {
+            println!("{}", visual(work).red());
+        } else {
+            println!("{}", visual(work));
+        }
+    }
+    if let Some(horizon) = cli.horizon {
+        println!();
+        for quarter in coordinates.upcoming_quarters(horizon) {
+            println!(
+                "{} starts {} ({} days away).",
+                quarter_label(&quarter, &cli).red().bold(),
+                format!("{}", quarter.start_of_quarter.format("%A, %d %B %Y"))
+                    .red()
+                    .bold(),
+                format!("{}", coordinates.days_until_start_of(&quarter))
+                    .red()
+                    .bold()
+            );
+        }
+    }
+    if cli.bar {
+        println!();
+        println!(
+            "{}",
+            urgency_colored(
+                visual(render_labelled_progress_bar(
+                    coordinates.percent_elapsed / 100.0,
+                    BAR_WIDTH,
+                    cli.bar_chars.unwrap_or_default(),
+                    cli.percent_position
+                )),
+                cli.urgency_thresholds.color(coordinates.percent_remaining)
+            )
+        );
+    }
+    if cli.year_bars {
+        println!();
+        println!(
+            "{}",
+            visual(render_year_bars(
+                &coordinates,
+                BAR_WIDTH,
+                cli.bar_chars.unwrap_or_default(),
+                cli.first_quarter_number
+            ))
+        );
+    }
+    if cli.battery {
+        println!();
+        let battery = visual(render_battery(coordinates.percent_remaining, BATTERY_WIDTH));
+        if coordinates.percent_remaining < LOW_BATTERY_PERCENT {
+            println!("{}", battery.red());
+        } else {
+            println!("{}", battery);
+        }
+    }
+    if cli.timeline {
+        println!();
+        println!("{}", visual(render_timeline(&coordinates, TIMELINE_WIDTH)));
+    }
+    if cli.calendar {
+        println!();
+        let today = format!("[{:>2}]", coordinates.generation_time.day());
+        println!(
+            "{}",
+            render_quarter_calendar(&coordinates).replacen(
+                &today,
+                &today.green().bold().to_string(),
+                1
+            )
+        );
+    }
+    if cli.weeks_grid {
+        println!();
+        let current = visual("[▶]".to_string());
+        println!(
+            "{}",
+            visual(render_weeks_grid(&coordinates)).replacen(
+                &current,
+                &current.green().bold().to_string(),
+                1
+            )
+        );
+    }
+    if cli.fiscal_year_bar {
+        println!();
+        println!(
+            "{}",
+            visual(render_fiscal_year_timeline(
+                &coordinates,
+                TIMELINE_WIDTH,
+                cli.first_quarter_number
+            ))
+        );
+    }
+    if cli.sprint_schedule {
+        println!();
+        print_sprint_schedule(&coordinates, visual);
+    }
+    if let Some(message) = &cli.quarter_end_message {
+        if coordinates.is_last_day_of_quarter() {
+            println!();
+            println!("{}", message.bold());
+        }
+    }
+    if alert {
+        process::exit(ALERT_EXIT_CODE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[cfg(unix)]
     #[test]
-    fn test_days_in_quarter() {
-        let first_day_q2 = DateTime::parse_from_rfc3339("1999-04-01T16:39:57+00:00").unwrap();
-        assert_eq!(generate_coordinates(&first_day_q2).days_in_quarter, 90);
+    fn test_exec_sets_environment() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let command: Vec<String> = ["sh", "-c", "echo $CC_QUARTER $CC_QUARTER_ID"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let output = command_with_env(&command, &generate_coordinates(&t))
+            .output()
+            .unwrap();
+        assert_eq!("2 1999Q2\n", String::from_utf8_lossy(&output.stdout));
     }
 }
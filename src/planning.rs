@@ -0,0 +1,495 @@
+use chrono::prelude::*;
+use chrono::{Days, Months};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::CorporateCoordinates;
+
+/// One sprint of a quarter's schedule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sprint {
+    pub number: u32,
+    pub name: String,
+    pub start: NaiveDate,
+    /// The last day of the sprint, inclusive.
+    pub end: NaiveDate,
+    /// Whether the sprint contains `generation_time`.
+    pub is_current: bool,
+}
+
+/// How close the quarter is to running out of time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RiskLevel {
+    OnTrack,
+    AtRisk,
+    Critical,
+}
+
+impl RiskLevel {
+    /// The Slack attachment color: `good`, `warning` or `danger`, which Slack
+    /// shows as green, yellow and red.
+    pub fn slack_color(&self) -> &'static str {
+        match self {
+            RiskLevel::OnTrack => "good",
+            RiskLevel::AtRisk => "warning",
+            RiskLevel::Critical => "danger",
+        }
+    }
+}
+
+impl fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            RiskLevel::OnTrack => "ON TRACK",
+            RiskLevel::AtRisk => "AT RISK",
+            RiskLevel::Critical => "CRITICAL",
+        })
+    }
+}
+
+impl CorporateCoordinates {
+    /// Working hours left in the quarter, counting today, at `hours_per_day`
+    /// per business day.
+    #[must_use]
+    pub fn remaining_working_hours(&self, hours_per_day: f64) -> f64 {
+        self.business_days_left_in_quarter as f64 * hours_per_day
+    }
+
+    /// Working hours already gone in the quarter, before today, at
+    /// `hours_per_day` per business day.
+    #[must_use]
+    pub fn elapsed_working_hours(&self, hours_per_day: f64) -> f64 {
+        (self.total_business_days_in_quarter - self.business_days_left_in_quarter) as f64
+            * hours_per_day
+    }
+
+    /// How many of `total_goals` should be done by now if they are completed
+    /// linearly over the quarter.
+    #[must_use]
+    pub fn sprint_goals_needed(&self, total_goals: u32) -> u32 {
+        (self.percent_elapsed / 100.0 * total_goals as f64).ceil() as u32
+    }
+
+    /// Whether `completed` of `total` goals keeps up with linear progress.
+    #[must_use]
+    pub fn goals_on_track(&self, completed: u32, total: u32) -> bool {
+        completed >= self.sprint_goals_needed(total)
+    }
+
+    /// Whether `spent` of `budget` is being spent no faster than the quarter
+    /// is elapsing.
+    #[must_use]
+    pub fn budget_on_track(&self, spent: f64, budget: f64) -> bool {
+        spent / budget <= self.percent_elapsed / 100.0
+    }
+
+    /// Percentage points the quarter elapsed is ahead (positive) or behind
+    /// (negative) of `target_percent`.
+    #[must_use]
+    pub fn relative_to_target(&self, target_percent: f64) -> f64 {
+        self.percent_elapsed - target_percent
+    }
+
+    /// Days left before `target_completion_day` (ahead, positive) or past it
+    /// (behind, negative). Without a target, the last day of the quarter is
+    /// the target.
+    #[must_use]
+    pub fn days_ahead_of_linear_schedule(&self) -> f64 {
+        let target = self
+            .target_completion_day
+            .unwrap_or(self.day_of_quarter + self.days_remaining_after_today);
+        target as f64 - self.day_of_quarter as f64
+    }
+
+    /// How many days each percentage point of the quarter lasts.
+    #[must_use]
+    pub fn days_per_percent(&self) -> f64 {
+        self.days_in_quarter as f64 / 100.0
+    }
+
+    /// Average business days per week over the quarter, about 5.0 for a
+    /// standard calendar.
+    #[must_use]
+    pub fn working_days_per_week(&self) -> f64 {
+        self.total_business_days_in_quarter as f64 / self.weeks_in_quarter as f64
+    }
+
+    /// `OnTrack` with more than a quarter of the days left, `AtRisk` down to
+    /// 10%, then `Critical`, as it always is on the last day.
+    #[must_use]
+    pub fn risk_level(&self) -> RiskLevel {
+        let days_left = self.days_left_in_quarter as f64;
+        let days_in_quarter = self.days_in_quarter as f64;
+        if self.is_last_day_of_quarter() || days_left < 0.10 * days_in_quarter {
+            RiskLevel::Critical
+        } else if days_left > 0.25 * days_in_quarter {
+            RiskLevel::OnTrack
+        } else {
+            RiskLevel::AtRisk
+        }
+    }
+
+    /// The quarter's sprints of `sprint_length` days, starting on the first
+    /// `start_weekday` of the quarter. The last sprint is cut short at the
+    /// end of the quarter. Sprints are named from `sprint_names` in order,
+    /// then `Sprint N` once the names run out.
+    #[must_use]
+    pub fn sprint_schedule(
+        &self,
+        sprint_length: u32,
+        start_weekday: Weekday,
+        sprint_names: &[String],
+    ) -> Vec<Sprint> {
+        if sprint_length == 0 {
+            return Vec::new();
+        }
+        let first_day = self.start_of_quarter.date_naive();
+        let last_day = self.end_of_quarter.date_naive();
+        let today = self.generation_time.date_naive();
+        let lead_in = (7 + start_weekday.num_days_from_monday()
+            - first_day.weekday().num_days_from_monday())
+            % 7;
+        let starts = first_day
+            .iter_days()
+            .skip(lead_in as usize)
+            .step_by(sprint_length as usize)
+            .take_while(|start| *start <= last_day);
+        starts
+            .zip(1..)
+            .map(|(start, number)| {
+                let end = (start + Days::new(sprint_length as u64 - 1)).min(last_day);
+                Sprint {
+                    number,
+                    name: sprint_names
+                        .get(number as usize - 1)
+                        .cloned()
+                        .unwrap_or_else(|| format!("Sprint {}", number)),
+                    start,
+                    end,
+                    is_current: (start..=end).contains(&today),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns how many days ahead (positive) or behind (negative) of perfectly
+/// even progress through the quarter a completion fraction of `target` is,
+/// given `elapsed_fraction` of the quarter has passed.
+pub fn days_off_pace(elapsed_fraction: f64, target: f64, days_in_quarter: u32) -> f64 {
+    (target - elapsed_fraction) * days_in_quarter as f64
+}
+
+/// Whole calendar months from `now` until `end`, e.g. 2 from the first
+/// moment of a calendar quarter until midnight on its last day.
+pub fn full_months_left(now: &DateTime<FixedOffset>, end: &DateTime<FixedOffset>) -> u32 {
+    (1..)
+        .take_while(|&months| {
+            now.checked_add_months(Months::new(months))
+                .is_some_and(|later| later <= *end)
+        })
+        .count() as u32
+}
+
+/// Days needed to finish `remaining_items` at `daily_rate` items per day, or
+/// `None` if the rate is not positive.
+pub fn forecast_days_to_finish(remaining_items: f64, daily_rate: f64) -> Option<f64> {
+    (daily_rate > 0.0).then(|| remaining_items / daily_rate)
+}
+
+/// Scores (0-100) how well `completion` (0-1) of the work keeps pace with
+/// `calendar_frac` (0-1) of the quarter elapsed. Exactly on pace scores 50;
+/// every percentage point ahead or behind moves the score by one.
+pub fn health_score(calendar_frac: f64, completion: f64) -> u32 {
+    (50.0 + (completion - calendar_frac) * 100.0)
+        .round()
+        .clamp(0.0, 100.0) as u32
+}
+
+/// A one-line verdict for a `health_score`.
+pub fn health_verdict(score: u32) -> &'static str {
+    match score {
+        0..=39 => "behind pace",
+        40..=60 => "on track",
+        _ => "ahead of pace",
+    }
+}
+
+/// How much of the quarter's work falls in each of its three months.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhaseWeights(pub [f64; 3]);
+
+impl FromStr for PhaseWeights {
+    type Err = String;
+
+    /// Parses three weights that aren't negative and don't sum to zero,
+    /// e.g. `1,2,3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let weights = s
+            .split(',')
+            .map(|weight| weight.trim().parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|e| format!("{:?} is not a list of weights: {}", s, e))?;
+        match weights[..] {
+            [_, _, _] if weights.iter().any(|w| !w.is_finite() || *w < 0.0) => Err(format!(
+                "{:?} has a weight that is negative or not a number",
+                s
+            )),
+            [_, _, _] if weights.iter().sum::<f64>() == 0.0 => {
+                Err(format!("the weights in {:?} add up to zero", s))
+            }
+            [first, second, third] => Ok(PhaseWeights([first, second, third])),
+            _ => Err(format!("expected three weights, got {:?}", s)),
+        }
+    }
+}
+
+/// Percentage (0-100) of the quarter between `bounds` that has passed at
+/// `now`, with each of its three months counting in proportion to `weights`
+/// rather than to its length.
+pub fn weighted_progress(
+    now: &DateTime<FixedOffset>,
+    bounds: (DateTime<FixedOffset>, DateTime<FixedOffset>),
+    weights: [f64; 3],
+) -> f64 {
+    let (start, end) = bounds;
+    let month_starts = [
+        start,
+        start.checked_add_months(Months::new(1)).unwrap(),
+        start.checked_add_months(Months::new(2)).unwrap(),
+        end,
+    ];
+    let progress: f64 = weights
+        .iter()
+        .zip(month_starts.windows(2))
+        .map(|(weight, month)| {
+            let length = month[1].signed_duration_since(month[0]).num_seconds() as f64;
+            let elapsed = now.signed_duration_since(month[0]).num_seconds() as f64;
+            weight * (elapsed / length).clamp(0.0, 1.0)
+        })
+        .sum();
+    progress / weights.iter().sum::<f64>() * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_coordinates, CorporateCoordinatesBuilder};
+
+    #[test]
+    fn test_remaining_working_hours() {
+        let t = DateTime::parse_from_rfc3339("1999-06-28T16:39:57+00:00").unwrap();
+        // Monday 28th to Wednesday 30th June.
+        assert_eq!(24.0, generate_coordinates(&t).remaining_working_hours(8.0));
+    }
+
+    #[test]
+    fn test_elapsed_and_remaining_working_hours_sum_to_total() {
+        for date in [
+            "1999-01-01T00:00:00+00:00",
+            "1999-02-13T09:15:00-05:00",
+            "2000-02-29T23:59:59+09:00",
+            "2024-06-30T12:00:00+00:00",
+            "2025-11-17T16:39:57+01:00",
+        ] {
+            let coordinates = generate_coordinates(&DateTime::parse_from_rfc3339(date).unwrap());
+            let total = coordinates.total_business_days_in_quarter as f64 * 7.5;
+            let sum =
+                coordinates.elapsed_working_hours(7.5) + coordinates.remaining_working_hours(7.5);
+            assert!((total - sum).abs() < 1e-9, "{}", date);
+        }
+    }
+
+    #[test]
+    fn test_sprint_goals_needed() {
+        let t = DateTime::parse_from_rfc3339("1999-05-14T16:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        assert_eq!(48, coordinates.percent_elapsed.round() as u32);
+        assert_eq!(5, coordinates.sprint_goals_needed(10));
+        assert_eq!(0, coordinates.sprint_goals_needed(0));
+    }
+
+    #[test]
+    fn test_goals_on_track() {
+        let t = DateTime::parse_from_rfc3339("1999-05-14T16:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        assert!(!coordinates.goals_on_track(4, 10));
+        assert!(coordinates.goals_on_track(5, 10));
+        assert!(coordinates.goals_on_track(6, 10));
+    }
+
+    #[test]
+    fn test_budget_on_track() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        assert!(coordinates.budget_on_track(400.0, 1000.0));
+        assert!(coordinates.budget_on_track(500.0, 1000.0));
+        assert!(!coordinates.budget_on_track(600.0, 1000.0));
+    }
+
+    #[test]
+    fn test_relative_to_target() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        assert_eq!(0.0, coordinates.relative_to_target(50.0));
+        assert!((coordinates.relative_to_target(51.65) - -1.65).abs() < 1e-9);
+        assert!((coordinates.relative_to_target(40.0) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_days_ahead_of_linear_schedule() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let on_day = |day| {
+            CorporateCoordinatesBuilder::new(t)
+                .target_completion_day(day)
+                .build()
+                .unwrap()
+                .days_ahead_of_linear_schedule()
+        };
+        // 16th May is day 46 of Q2.
+        assert_eq!(34.0, on_day(80));
+        assert_eq!(-6.0, on_day(40));
+        assert_eq!(
+            45.0,
+            generate_coordinates(&t).days_ahead_of_linear_schedule()
+        );
+    }
+
+    #[test]
+    fn test_days_per_percent() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        assert_eq!(90, coordinates.days_in_quarter);
+        assert_eq!("0.90", format!("{:.2}", coordinates.days_per_percent()));
+    }
+
+    #[test]
+    fn test_working_days_per_week() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(5.0, generate_coordinates(&t).working_days_per_week());
+    }
+
+    #[test]
+    fn test_weighted_progress_back_loaded() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T00:00:00+00:00").unwrap();
+        let coordinates = generate_coordinates(&t);
+        let bounds = (coordinates.start_of_quarter, coordinates.end_of_quarter);
+
+        let even = weighted_progress(&t, bounds, [1.0, 1.0, 1.0]);
+        let back_loaded = weighted_progress(&t, bounds, [1.0, 2.0, 3.0]);
+        assert!((even - 49.0).abs() < 1.0);
+        assert!(back_loaded < coordinates.percent_elapsed);
+        assert!((back_loaded - 32.8).abs() < 0.1);
+        assert_eq!(100.0, weighted_progress(&bounds.1, bounds, [1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn test_parse_phase_weights() {
+        assert_eq!(Ok(PhaseWeights([1.0, 2.0, 3.0])), "1, 2,3".parse());
+        assert_eq!(Ok(PhaseWeights([0.0, 0.0, 1.0])), "0,0,1".parse());
+        for invalid in [
+            "0,0,0", "1,-1,2", "1,NaN,2", "1,inf,2", "1,2", "1,2,3,4", "a,b,c",
+        ] {
+            assert!(invalid.parse::<PhaseWeights>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_forecast_days_to_finish() {
+        let days = forecast_days_to_finish(500.0, 8.5).unwrap();
+        assert_eq!("58.8", format!("{:.1}", days));
+        assert_eq!(None, forecast_days_to_finish(500.0, 0.0));
+    }
+
+    #[test]
+    fn test_risk_level() {
+        let at = |date: &str| {
+            generate_coordinates(
+                &DateTime::parse_from_rfc3339(&format!("{}T12:00:00+00:00", date)).unwrap(),
+            )
+            .risk_level()
+        };
+        // Q2 1999 is 90 days long.
+        assert_eq!(RiskLevel::OnTrack, at("1999-05-16"));
+        assert_eq!(RiskLevel::AtRisk, at("1999-06-08"));
+        assert_eq!(RiskLevel::AtRisk, at("1999-06-21"));
+        assert_eq!(RiskLevel::Critical, at("1999-06-22"));
+        assert_eq!(RiskLevel::Critical, at("1999-06-30"));
+        assert_eq!("AT RISK", RiskLevel::AtRisk.to_string());
+    }
+
+    #[test]
+    fn test_sprint_schedule() {
+        // Q2 1999 starts on a Thursday, so the first Monday sprint starts on
+        // the 5th of April.
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let names = ["Aardvark".to_string(), "Badger".to_string()];
+        let sprints = generate_coordinates(&t).sprint_schedule(14, Weekday::Mon, &names);
+        let date = |m, d| NaiveDate::from_ymd_opt(1999, m, d).unwrap();
+
+        assert_eq!(7, sprints.len());
+        assert_eq!(
+            Sprint {
+                number: 1,
+                name: "Aardvark".to_string(),
+                start: date(4, 5),
+                end: date(4, 18),
+                is_current: false,
+            },
+            sprints[0]
+        );
+        assert_eq!("Badger", sprints[1].name);
+        assert_eq!("Sprint 3", sprints[2].name);
+        assert_eq!(
+            vec![3],
+            sprints
+                .iter()
+                .filter(|sprint| sprint.is_current)
+                .map(|sprint| sprint.number)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            (date(6, 28), date(6, 30)),
+            (sprints[6].start, sprints[6].end)
+        );
+    }
+
+    #[test]
+    fn test_full_months_left() {
+        let at = |time: &str| {
+            let coordinates = generate_coordinates(&DateTime::parse_from_rfc3339(time).unwrap());
+            full_months_left(&coordinates.generation_time, &coordinates.end_of_quarter)
+        };
+        assert_eq!(2, at("1999-04-01T00:00:00+00:00"));
+        assert_eq!(1, at("1999-05-16T16:39:57+00:00"));
+        assert_eq!(0, at("1999-06-15T12:00:00+00:00"));
+    }
+
+    #[test]
+    fn test_days_off_pace() {
+        assert_eq!(-13.5, days_off_pace(0.25, 0.10, 90));
+        assert_eq!(13.5, days_off_pace(0.10, 0.25, 90));
+        assert_eq!(0.0, days_off_pace(0.5, 0.5, 90));
+    }
+
+    #[test]
+    fn test_health_score_on_track() {
+        assert_eq!(50, health_score(0.4, 0.4));
+        assert_eq!(52, health_score(0.4, 0.42));
+        assert_eq!("on track", health_verdict(health_score(0.4, 0.42)));
+    }
+
+    #[test]
+    fn test_health_score_ahead() {
+        assert_eq!(80, health_score(0.25, 0.55));
+        assert_eq!(100, health_score(0.1, 0.9));
+        assert_eq!("ahead of pace", health_verdict(health_score(0.25, 0.55)));
+    }
+
+    #[test]
+    fn test_health_score_behind() {
+        assert_eq!(25, health_score(0.5, 0.25));
+        assert_eq!(0, health_score(0.9, 0.1));
+        assert_eq!("behind pace", health_verdict(health_score(0.5, 0.25)));
+    }
+}
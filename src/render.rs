@@ -0,0 +1,919 @@
+use chrono::prelude::*;
+use chrono::Months;
+use chrono_tz::{OffsetName, Tz};
+use clap::ValueEnum;
+use std::str::FromStr;
+
+use crate::CorporateCoordinates;
+
+/// Renders the quarter as a horizontal timeline `width` columns wide, with
+/// month labels above a track that has a tick at each month boundary and a
+/// marker at the generation time.
+pub fn render_timeline(coordinates: &CorporateCoordinates, width: usize) -> String {
+    let start = coordinates.start_of_quarter;
+    let months = (0..3).map(|month| {
+        let month_start = start.checked_add_months(Months::new(month)).unwrap();
+        (month_start, month_start.format("%b").to_string())
+    });
+    render_track(
+        (start, coordinates.end_of_quarter),
+        months,
+        coordinates.generation_time,
+        width,
+    )
+}
+
+/// Renders the whole fiscal year like `render_timeline`, with a tick and
+/// label at the start of each quarter. Quarters are numbered from
+/// `first_quarter_number`.
+pub fn render_fiscal_year_timeline(
+    coordinates: &CorporateCoordinates,
+    width: usize,
+    first_quarter_number: u32,
+) -> String {
+    let start = coordinates
+        .generation_time
+        .offset()
+        .from_local_datetime(
+            &coordinates
+                .start_of_fiscal_year()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        )
+        .unwrap();
+    let quarter_start = |quarter: u32| start.checked_add_months(Months::new(quarter * 3)).unwrap();
+    let quarters = (0..4).map(|quarter| {
+        (
+            quarter_start(quarter),
+            format!("Q{}", quarter + first_quarter_number),
+        )
+    });
+    render_track(
+        (start, quarter_start(4)),
+        quarters,
+        coordinates.generation_time,
+        width,
+    )
+}
+
+/// Labels above a track between `bounds`, ticked at each of `ticks`, with a
+/// marker at `now`.
+fn render_track(
+    bounds: (DateTime<FixedOffset>, DateTime<FixedOffset>),
+    ticks: impl Iterator<Item = (DateTime<FixedOffset>, String)>,
+    now: DateTime<FixedOffset>,
+    width: usize,
+) -> String {
+    let width = width.max(2);
+    let (start, end) = bounds;
+    let span = end.signed_duration_since(start).num_seconds() as f64;
+    let column = |t: DateTime<FixedOffset>| {
+        let fraction = (t.signed_duration_since(start).num_seconds() as f64 / span).clamp(0.0, 1.0);
+        (fraction * (width - 1) as f64).round() as usize
+    };
+
+    let mut labels = vec![' '; width];
+    let mut track = vec!['─'; width];
+    for (time, label) in ticks {
+        let tick = column(time);
+        track[tick] = if tick == 0 { '├' } else { '┼' };
+        for (offset, c) in label.chars().enumerate() {
+            if let Some(label) = labels.get_mut(tick + offset) {
+                *label = c;
+            }
+        }
+    }
+    track[width - 1] = '┤';
+    track[column(now)] = '●';
+
+    format!(
+        "{}\n{}",
+        labels.into_iter().collect::<String>().trim_end(),
+        track.into_iter().collect::<String>()
+    )
+}
+
+/// Formats the generation time for display: with `time_format` if given,
+/// otherwise on a 12-hour clock if `twelve_hour`, otherwise as ISO 8601.
+pub fn format_generation_time(
+    time: &DateTime<FixedOffset>,
+    time_format: Option<&str>,
+    twelve_hour: bool,
+) -> String {
+    match (time_format, twelve_hour) {
+        (Some(time_format), _) => time.format(time_format).to_string(),
+        (None, true) => time.format("%Y-%m-%d %-I:%M %p %:z").to_string(),
+        (None, false) => time.format("%+").to_string(),
+    }
+}
+
+/// The offset of `time` in parentheses, preceded by the abbreviation `zone`
+/// uses at that instant when a named zone is given, e.g. `PDT (-07:00)`.
+pub fn format_zone(time: &DateTime<FixedOffset>, zone: Option<Tz>) -> String {
+    let abbreviation = zone.and_then(|zone| {
+        zone.offset_from_utc_datetime(&time.naive_utc())
+            .abbreviation()
+            .map(str::to_string)
+    });
+    match abbreviation {
+        Some(abbreviation) => format!("{} ({})", abbreviation, time.format("%:z")),
+        None => format!("({})", time.format("%:z")),
+    }
+}
+
+/// Replaces the Unicode glyphs used by the renderers with ASCII lookalikes,
+/// for terminals that can't be relied on to display UTF-8.
+pub fn to_ascii(rendered: &str) -> String {
+    rendered
+        .chars()
+        .map(|c| match c {
+            '█' => '#',
+            '░' | '─' => '-',
+            '├' | '┼' | '┤' => '|',
+            '●' => 'o',
+            '▲' => '^',
+            '▼' => 'v',
+            '▒' => '=',
+            '·' => '.',
+            '→' | '▶' => '>',
+            '■' => '#',
+            c if c.is_ascii() => c,
+            _ => '?',
+        })
+        .collect()
+}
+
+/// Removes the ANSI color codes from rendered output, e.g. before copying it
+/// to the clipboard.
+pub fn strip_colors(rendered: &str) -> String {
+    let mut plain = String::with_capacity(rendered.len());
+    let mut chars = rendered.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+/// The percent-remaining cutoffs below which the urgency color turns
+/// yellow, orange and red.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UrgencyThresholds {
+    pub yellow: f64,
+    pub orange: f64,
+    pub red: f64,
+}
+
+impl Default for UrgencyThresholds {
+    fn default() -> Self {
+        UrgencyThresholds {
+            yellow: 50.0,
+            orange: 25.0,
+            red: 10.0,
+        }
+    }
+}
+
+impl FromStr for UrgencyThresholds {
+    type Err = String;
+
+    /// Parses three descending percentages, e.g. `50,25,10`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cutoffs = s
+            .split(',')
+            .map(|cutoff| cutoff.trim().parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|e| format!("{:?} is not a list of percentages: {}", s, e))?;
+        match cutoffs[..] {
+            [yellow, orange, red] if yellow > orange && orange > red => Ok(UrgencyThresholds {
+                yellow,
+                orange,
+                red,
+            }),
+            [_, _, _] => Err(format!("{:?} is not in descending order", s)),
+            _ => Err(format!("expected three percentages, got {:?}", s)),
+        }
+    }
+}
+
+impl UrgencyThresholds {
+    /// The urgency color for how much of a period remains.
+    pub fn color(&self, percent_remaining: f64) -> &'static str {
+        if percent_remaining > self.yellow {
+            "green"
+        } else if percent_remaining > self.orange {
+            "yellow"
+        } else if percent_remaining > self.red {
+            "orange"
+        } else {
+            "red"
+        }
+    }
+}
+
+/// Returns the urgency color for how much of a period remains: green, then
+/// yellow at 50%, orange at 25% and red at 10%.
+pub fn urgency_color(percent_remaining: f64) -> &'static str {
+    UrgencyThresholds::default().color(percent_remaining)
+}
+
+/// Renders one progress bar per quarter of the (fiscal) year the coordinates
+/// are in: full for past quarters, partial for the current one and empty for
+/// those still to come. Quarters are numbered from `first_quarter_number`.
+pub fn render_year_bars(
+    coordinates: &CorporateCoordinates,
+    width: usize,
+    chars: BarChars,
+    first_quarter_number: u32,
+) -> String {
+    (1..=4)
+        .map(|quarter| {
+            let fraction = match quarter.cmp(&coordinates.quarter) {
+                std::cmp::Ordering::Less => 1.0,
+                std::cmp::Ordering::Equal => coordinates.percent_elapsed / 100.0,
+                std::cmp::Ordering::Greater => 0.0,
+            };
+            format!(
+                "Q{} {}",
+                quarter - 1 + first_quarter_number,
+                render_labelled_progress_bar(fraction, width, chars, PercentPosition::After)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// A battery `width` cells wide, charged to `percent_remaining`, e.g.
+/// `[███░░] 60%`.
+pub fn render_battery(percent_remaining: f64, width: usize) -> String {
+    format!(
+        "{} {:.0}%",
+        render_progress_bar(percent_remaining / 100.0, width, BarChars::default()),
+        percent_remaining
+    )
+}
+
+impl CorporateCoordinates {
+    /// A bar `width` cells wide spanning the fiscal year, a quarter at a time:
+    /// `█` for past quarters, `░` and `▒` for the elapsed and remaining parts
+    /// of this quarter, and `·` for future quarters.
+    #[must_use]
+    pub fn fiscal_year_progress_bar(&self, width: u32) -> String {
+        let width = width as usize;
+        let mut bar = String::from("[");
+        for quarter in 1..=4 {
+            let cells = width * quarter as usize / 4 - width * (quarter as usize - 1) / 4;
+            if quarter < self.quarter {
+                bar.push_str(&"█".repeat(cells));
+            } else if quarter > self.quarter {
+                bar.push_str(&"·".repeat(cells));
+            } else {
+                let elapsed = ((self.percent_elapsed / 100.0).clamp(0.0, 1.0) * cells as f64)
+                    .round() as usize;
+                bar.push_str(&"░".repeat(elapsed));
+                bar.push_str(&"▒".repeat(cells - elapsed));
+            }
+        }
+        bar.push(']');
+        bar
+    }
+}
+
+/// The day of the year (1-366) the quarter starts, ends and is generated on,
+/// e.g. `start 92, end 182, now 137`.
+pub fn format_days_of_year(coordinates: &CorporateCoordinates) -> String {
+    format!(
+        "start {}, end {}, now {}",
+        coordinates.start_of_quarter.ordinal(),
+        coordinates.end_of_quarter.ordinal(),
+        coordinates.generation_time.ordinal()
+    )
+}
+
+/// Languages whose decimal separator is a comma.
+const COMMA_DECIMAL_LANGUAGES: [&str; 22] = [
+    "bg", "ca", "cs", "da", "de", "el", "es", "et", "fi", "fr", "hr", "hu", "id", "it", "lt", "lv",
+    "nb", "nl", "pl", "pt", "ru", "sv",
+];
+
+/// The decimal separator of a locale like `de`, `de-DE` or `de_DE.UTF-8`.
+pub fn decimal_separator(locale: &str) -> char {
+    let language = locale
+        .split(['-', '_', '.'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if COMMA_DECIMAL_LANGUAGES.contains(&language.as_str()) {
+        ','
+    } else {
+        '.'
+    }
+}
+
+/// Formats `percent` to two decimal places with `locale`'s decimal
+/// separator, e.g. `47,30%` in German.
+pub fn format_percent(percent: f64, locale: Option<&str>) -> String {
+    let formatted = format!("{:.2}%", percent);
+    match locale.map(decimal_separator) {
+        Some(',') => formatted.replace('.', ","),
+        _ => formatted,
+    }
+}
+
+/// Formats `n` with commas between groups of thousands, e.g. `5,184,000`.
+pub fn group_thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// How a quarter is labelled in human-readable output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum LabelFormat {
+    /// `Q2, 2024`
+    #[default]
+    Prose,
+    /// `2024-Q2`
+    Iso,
+}
+
+impl LabelFormat {
+    pub fn label(&self, coordinates: &CorporateCoordinates) -> String {
+        self.format(coordinates.quarter, &coordinates.year)
+    }
+
+    /// Labels quarter number `quarter` of `year`, however they are written.
+    pub fn format(&self, quarter: u32, year: &str) -> String {
+        match self {
+            LabelFormat::Prose => format!("Q{}, {}", quarter, year),
+            LabelFormat::Iso => format!("{}-Q{}", year, quarter),
+        }
+    }
+}
+
+/// How much the human-readable summary says.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum SummaryStyle {
+    /// A few sentences about the quarter and the time now
+    #[default]
+    Full,
+    /// A single line with the percentage and days left
+    Short,
+}
+
+/// Whether elapsed days are counted in calendar or business days.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ElapsedBasis {
+    #[default]
+    Calendar,
+    Business,
+}
+
+impl ElapsedBasis {
+    /// Describes how far into the quarter we are, e.g. `Business day 38 of 62`.
+    pub fn day_of_quarter(&self, coordinates: &CorporateCoordinates) -> String {
+        match self {
+            ElapsedBasis::Calendar => format!(
+                "Day {} of {}",
+                coordinates.day_of_quarter,
+                coordinates.days_in_quarter + 1
+            ),
+            ElapsedBasis::Business => format!(
+                "Business day {} of {}",
+                coordinates.business_day_of_quarter, coordinates.total_business_days_in_quarter
+            ),
+        }
+    }
+}
+
+/// Where the percentage label goes relative to a progress bar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum PercentPosition {
+    Before,
+    #[default]
+    After,
+    None,
+}
+
+/// The characters a progress bar is drawn with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BarChars {
+    pub fill: char,
+    pub empty: char,
+}
+
+impl Default for BarChars {
+    fn default() -> Self {
+        BarChars {
+            fill: '█',
+            empty: '░',
+        }
+    }
+}
+
+impl FromStr for BarChars {
+    type Err = String;
+
+    /// Parses exactly two characters: the fill, then the empty character.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        match chars[..] {
+            [fill, empty] => Ok(BarChars { fill, empty }),
+            _ => Err(format!(
+                "expected exactly two characters (fill then empty), got {:?}",
+                s
+            )),
+        }
+    }
+}
+
+/// Renders `fraction` (0-1) as a bar `width` cells wide.
+pub fn render_progress_bar(fraction: f64, width: usize, chars: BarChars) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f64).round() as usize;
+    format!(
+        "[{}{}]",
+        chars.fill.to_string().repeat(filled),
+        chars.empty.to_string().repeat(width - filled)
+    )
+}
+
+/// Renders each week of the quarter as a cell labelled `W1`, `W2` and so on:
+/// `[■]` once it's over, `[▶]` for the current week and `[ ]` for those to
+/// come.
+pub fn render_weeks_grid(coordinates: &CorporateCoordinates) -> String {
+    // The last days of a quarter longer than its whole weeks belong to the
+    // last week.
+    let current = coordinates
+        .full_week_of_quarter_done
+        .min(coordinates.weeks_in_quarter.saturating_sub(1));
+    let (labels, cells): (String, String) = (0..coordinates.weeks_in_quarter)
+        .map(|week| {
+            let cell = match week.cmp(&current) {
+                std::cmp::Ordering::Less => "[■]",
+                std::cmp::Ordering::Equal => "[▶]",
+                std::cmp::Ordering::Greater => "[ ]",
+            };
+            (format!("{:<3}", format!("W{}", week + 1)), cell)
+        })
+        .unzip();
+    format!("{}\n{}", labels.trim_end(), cells)
+}
+
+/// Renders the three months of the quarter as small calendars, one under the
+/// other, with today and the last day of the quarter marked.
+pub fn render_quarter_calendar(coordinates: &CorporateCoordinates) -> String {
+    let start = coordinates.start_of_quarter.date_naive();
+    (0..3)
+        .map(|month| {
+            render_month_grid(
+                start.checked_add_months(Months::new(month)).unwrap(),
+                coordinates.generation_time.date_naive(),
+                coordinates.end_of_quarter.date_naive(),
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// Renders the month containing `month` as a grid of weeks starting on
+/// Monday, with `[16]` marking `today` and `(30)` marking `quarter_end`.
+pub fn render_month_grid(month: NaiveDate, today: NaiveDate, quarter_end: NaiveDate) -> String {
+    let first = month.with_day(1).unwrap();
+    let mut lines = vec![
+        first.format("%B %Y").to_string(),
+        " Mo  Tu  We  Th  Fr  Sa  Su".to_string(),
+    ];
+    let mut week = "    ".repeat(first.weekday().num_days_from_monday() as usize);
+    for day in first
+        .iter_days()
+        .take_while(|day| day.month() == first.month())
+    {
+        let (open, close) = if day == today {
+            ('[', ']')
+        } else if day == quarter_end {
+            ('(', ')')
+        } else {
+            (' ', ' ')
+        };
+        week.push_str(&format!("{}{:>2}{}", open, day.day(), close));
+        if day.weekday() == Weekday::Sun {
+            lines.push(week.trim_end().to_string());
+            week.clear();
+        }
+    }
+    if !week.is_empty() {
+        lines.push(week.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+/// Renders calendar progress over reported completion as `Time` and `Work`
+/// bars, so the gap between them stands out.
+pub fn render_plan_vs_actual(
+    time_fraction: f64,
+    work_fraction: f64,
+    width: usize,
+    chars: BarChars,
+) -> [String; 2] {
+    [("Time", time_fraction), ("Work", work_fraction)].map(|(label, fraction)| {
+        format!(
+            "{} {}",
+            label,
+            render_labelled_progress_bar(fraction, width, chars, PercentPosition::After)
+        )
+    })
+}
+
+/// Renders a progress bar with its percentage label placed per `position`.
+pub fn render_labelled_progress_bar(
+    fraction: f64,
+    width: usize,
+    chars: BarChars,
+    position: PercentPosition,
+) -> String {
+    let bar = render_progress_bar(fraction, width, chars);
+    let label = format!("{:.2}%", fraction * 100.0);
+    match position {
+        PercentPosition::Before => format!("{} {}", label, bar),
+        PercentPosition::After => format!("{} {}", bar, label),
+        PercentPosition::None => bar,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_coordinates;
+
+    #[test]
+    fn test_timeline_marker_matches_elapsed_fraction() {
+        let width = 60;
+        for date in [
+            "1999-04-01T16:39:57+00:00",
+            "1999-05-15T16:39:57+00:00",
+            "1999-06-29T16:39:57+00:00",
+        ] {
+            let coordinates = generate_coordinates(&DateTime::parse_from_rfc3339(date).unwrap());
+            let elapsed = coordinates
+                .generation_time
+                .signed_duration_since(coordinates.start_of_quarter)
+                .num_seconds() as f64
+                / coordinates
+                    .end_of_quarter
+                    .signed_duration_since(coordinates.start_of_quarter)
+                    .num_seconds() as f64;
+
+            let timeline = render_timeline(&coordinates, width);
+            let track = timeline.lines().nth(1).unwrap();
+            let marker = track.chars().position(|c| c == '●').unwrap();
+            assert!((marker as f64 - elapsed * (width - 1) as f64).abs() <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_fiscal_year_timeline() {
+        let width = 49;
+        let t = DateTime::parse_from_rfc3339("1999-05-15T16:39:57+00:00").unwrap();
+        // The fiscal year starting in October 1998 is 365 days long.
+        let coordinates = crate::generate_fiscal_coordinates(&t, 10);
+        let elapsed = t
+            .signed_duration_since(
+                DateTime::parse_from_rfc3339("1998-10-01T00:00:00+00:00").unwrap(),
+            )
+            .num_seconds() as f64
+            / (365.0 * 24.0 * 60.0 * 60.0);
+
+        let timeline = render_fiscal_year_timeline(&coordinates, width, 1);
+        let mut lines = timeline.lines();
+        let labels = lines.next().unwrap();
+        let track: Vec<char> = lines.next().unwrap().chars().collect();
+
+        let marker = track.iter().position(|c| *c == '●').unwrap();
+        assert!((marker as f64 - elapsed * (width - 1) as f64).abs() <= 1.0);
+        assert_eq!(3, track.iter().filter(|c| **c == '┼').count());
+        assert_eq!(('├', '┤'), (track[0], track[width - 1]));
+        assert_eq!(Some(0), labels.find("Q1"));
+        assert!(labels.contains("Q4"));
+
+        let from_zero = render_fiscal_year_timeline(&coordinates, width, 0);
+        let labels = from_zero.lines().next().unwrap();
+        assert_eq!(Some(0), labels.find("Q0"));
+        assert!(labels.contains("Q3"));
+        assert!(!labels.contains("Q4"));
+    }
+
+    #[test]
+    fn test_plan_vs_actual_work_trails_time() {
+        let [time, work] = render_plan_vs_actual(0.6, 0.3, 20, BarChars::default());
+        let filled = |bar: &str| {
+            bar.chars()
+                .filter(|c| *c == BarChars::default().fill)
+                .count()
+        };
+        assert!(time.starts_with("Time ["));
+        assert!(work.starts_with("Work ["));
+        assert_eq!(12, filled(&time));
+        assert_eq!(6, filled(&work));
+    }
+
+    #[test]
+    fn test_weeks_grid_on_last_day_of_quarter() {
+        // Q3 1999 has 92 days, so its last day is past the 13th full week.
+        let t = DateTime::parse_from_rfc3339("1999-09-30T12:00:00+00:00").unwrap();
+        let grid = render_weeks_grid(&generate_coordinates(&t));
+        assert_eq!(
+            format!("{}[▶]", "[■]".repeat(12)),
+            grid.lines().nth(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_first_quarter_number() {
+        let january = DateTime::parse_from_rfc3339("1999-01-16T16:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&january);
+        let label = |format: LabelFormat, first| {
+            format.format(coordinates.quarter_number_from(first), &coordinates.year)
+        };
+        assert_eq!("Q0, 1999", label(LabelFormat::Prose, 0));
+        assert_eq!("1999-Q0", label(LabelFormat::Iso, 0));
+        assert_eq!(
+            LabelFormat::Prose.label(&coordinates),
+            label(LabelFormat::Prose, 1)
+        );
+    }
+
+    #[test]
+    fn test_weeks_grid() {
+        // The 16th of May is in the seventh week of Q2 1999.
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let grid = render_weeks_grid(&generate_coordinates(&t));
+        let mut lines = grid.lines();
+        let labels = lines.next().unwrap();
+        let cells = lines.next().unwrap();
+
+        assert!(labels.starts_with("W1 W2 W3"));
+        assert!(labels.ends_with("W12W13"));
+        assert_eq!(39, cells.chars().count());
+        assert_eq!(format!("{}[▶]{}", "[■]".repeat(6), "[ ]".repeat(6)), cells);
+    }
+
+    #[test]
+    fn test_month_grid() {
+        let date = |m, d| NaiveDate::from_ymd_opt(1999, m, d).unwrap();
+        let grid = render_month_grid(date(5, 1), date(5, 16), date(6, 30));
+        let lines: Vec<&str> = grid.lines().collect();
+
+        assert_eq!("May 1999", lines[0]);
+        // The 1st of May 1999 was a Saturday, so the month spans six weeks.
+        assert_eq!(8, lines.len());
+        assert_eq!("                      1   2", lines[2]);
+        let days: Vec<&str> = lines[2..]
+            .iter()
+            .flat_map(|week| week.split_whitespace())
+            .collect();
+        assert_eq!(31, days.len());
+        assert_eq!(
+            vec!["[16]"],
+            days.iter()
+                .filter(|d| d.starts_with('['))
+                .copied()
+                .collect::<Vec<_>>()
+        );
+        assert!(!grid.contains('('));
+
+        let june = render_month_grid(date(6, 1), date(5, 16), date(6, 30));
+        assert!(june.ends_with("(30)"));
+    }
+
+    #[test]
+    fn test_timeline_month_ticks() {
+        let q2 = DateTime::parse_from_rfc3339("1999-05-15T16:39:57+00:00").unwrap();
+        let timeline = render_timeline(&generate_coordinates(&q2), 91);
+        let mut lines = timeline.lines();
+        let labels = lines.next().unwrap();
+        let track: Vec<char> = lines.next().unwrap().chars().collect();
+
+        assert_eq!(Some(0), labels.find("Apr"));
+        assert_eq!(Some(30), labels.find("May"));
+        assert_eq!(Some(61), labels.find("Jun"));
+        assert_eq!('├', track[0]);
+        assert_eq!('┼', track[30]);
+        assert_eq!('┼', track[61]);
+        assert_eq!('┤', track[90]);
+    }
+
+    #[test]
+    fn test_elapsed_basis() {
+        let monday = DateTime::parse_from_rfc3339("1999-04-05T16:39:57+00:00").unwrap();
+        let coordinates = generate_coordinates(&monday);
+        assert_eq!(
+            "Day 5 of 91",
+            ElapsedBasis::Calendar.day_of_quarter(&coordinates)
+        );
+        assert_eq!(
+            "Business day 3 of 65",
+            ElapsedBasis::Business.day_of_quarter(&coordinates)
+        );
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!("[░░░░]", render_progress_bar(0.0, 4, BarChars::default()));
+        assert_eq!("[██░░]", render_progress_bar(0.5, 4, BarChars::default()));
+        assert_eq!("[████]", render_progress_bar(1.0, 4, BarChars::default()));
+    }
+
+    #[test]
+    fn test_custom_bar_chars() {
+        let chars: BarChars = "=-".parse().unwrap();
+        assert_eq!("[==--]", render_progress_bar(0.5, 4, chars));
+        assert_eq!(BarChars::default(), "█░".parse().unwrap());
+        assert!("=".parse::<BarChars>().is_err());
+        assert!("=-+".parse::<BarChars>().is_err());
+    }
+
+    #[test]
+    fn test_percent_position_before() {
+        assert_eq!(
+            "25.00% [█░░░]",
+            render_labelled_progress_bar(0.25, 4, BarChars::default(), PercentPosition::Before)
+        );
+    }
+
+    #[test]
+    fn test_percent_position_after() {
+        assert_eq!(
+            "[█░░░] 25.00%",
+            render_labelled_progress_bar(0.25, 4, BarChars::default(), PercentPosition::After)
+        );
+    }
+
+    #[test]
+    fn test_percent_position_none() {
+        assert_eq!(
+            "[█░░░]",
+            render_labelled_progress_bar(0.25, 4, BarChars::default(), PercentPosition::None)
+        );
+    }
+
+    #[test]
+    fn test_format_generation_time() {
+        let t = DateTime::parse_from_rfc3339("1999-05-15T14:00:00+01:00").unwrap();
+        assert_eq!(
+            "1999-05-15 2:00 PM +01:00",
+            format_generation_time(&t, None, true)
+        );
+        assert_eq!(
+            "1999-05-15T14:00:00+01:00",
+            format_generation_time(&t, None, false)
+        );
+        assert_eq!("14h", format_generation_time(&t, Some("%Hh"), true));
+    }
+
+    #[test]
+    fn test_format_zone() {
+        let t = DateTime::parse_from_rfc3339("1999-05-15T07:00:00-07:00").unwrap();
+        assert_eq!(
+            "PDT (-07:00)",
+            format_zone(&t, Some(chrono_tz::America::Los_Angeles))
+        );
+        assert_eq!("(-07:00)", format_zone(&t, None));
+    }
+
+    #[test]
+    fn test_format_days_of_year() {
+        let leap = DateTime::parse_from_rfc3339("2024-05-16T12:00:00+00:00").unwrap();
+        assert_eq!(
+            "start 92, end 182, now 137",
+            format_days_of_year(&generate_coordinates(&leap))
+        );
+        let common = DateTime::parse_from_rfc3339("2025-05-16T12:00:00+00:00").unwrap();
+        assert_eq!(
+            "start 91, end 181, now 136",
+            format_days_of_year(&generate_coordinates(&common))
+        );
+    }
+
+    #[test]
+    fn test_render_battery() {
+        assert_eq!("[█████████░] 90%", render_battery(90.0, 10));
+        assert_eq!("[█░░░░░░░░░] 12%", render_battery(12.0, 10));
+        assert_eq!("[░░░░░] 0%", render_battery(0.0, 5));
+    }
+
+    #[test]
+    fn test_fiscal_year_progress_bar() {
+        let t = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        assert_eq!(
+            "[██████████░░░░░▒▒▒▒▒····················]",
+            generate_coordinates(&t).fiscal_year_progress_bar(40)
+        );
+
+        // Q1 of a fiscal year starting in October.
+        let t = DateTime::parse_from_rfc3339("1999-10-01T00:00:00+00:00").unwrap();
+        assert_eq!(
+            "[▒▒▒············]",
+            crate::generate_fiscal_coordinates(&t, 10).fiscal_year_progress_bar(15)
+        );
+    }
+
+    #[test]
+    fn test_format_percent() {
+        assert_eq!("47,30%", format_percent(47.3, Some("de")));
+        assert_eq!("47,30%", format_percent(47.3, Some("de_DE.UTF-8")));
+        assert_eq!("47.30%", format_percent(47.3, Some("en-GB")));
+        assert_eq!("47.30%", format_percent(47.3, None));
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        let q2 = DateTime::parse_from_rfc3339("1999-05-15T16:39:57+00:00").unwrap();
+        let rendered = format!(
+            "{}\n{}",
+            render_timeline(&generate_coordinates(&q2), 60),
+            render_labelled_progress_bar(0.5, 10, BarChars::default(), PercentPosition::After)
+        );
+        assert!(!rendered.is_ascii());
+
+        let ascii = to_ascii(&rendered);
+        assert!(ascii.is_ascii());
+        assert!(ascii.ends_with("[#####-----] 50.00%"));
+    }
+
+    #[test]
+    fn test_strip_colors() {
+        assert_eq!(
+            "There is 50.00% of the quarter remaining.\n",
+            strip_colors("There is \x1b[1;31m50.00%\x1b[0m of the quarter remaining.\n")
+        );
+        assert_eq!("Q2, 1999 ▶", strip_colors("Q2, 1999 ▶"));
+    }
+
+    #[test]
+    fn test_urgency_color() {
+        assert_eq!("green", urgency_color(80.0));
+        assert_eq!("yellow", urgency_color(50.0));
+        assert_eq!("orange", urgency_color(25.0));
+        assert_eq!("red", urgency_color(10.0));
+    }
+
+    #[test]
+    fn test_custom_urgency_thresholds() {
+        let thresholds: UrgencyThresholds = "70,40,20".parse().unwrap();
+        assert_eq!("yellow", thresholds.color(60.0));
+        assert_eq!("orange", thresholds.color(30.0));
+        assert_eq!("red", thresholds.color(15.0));
+        assert_eq!("green", urgency_color(60.0));
+
+        assert!("10,25,50".parse::<UrgencyThresholds>().is_err());
+        assert!("50,25".parse::<UrgencyThresholds>().is_err());
+        assert!("50,lots,10".parse::<UrgencyThresholds>().is_err());
+    }
+
+    #[test]
+    fn test_year_bars() {
+        let q2 = DateTime::parse_from_rfc3339("1999-05-16T16:39:57+00:00").unwrap();
+        let bars = render_year_bars(&generate_coordinates(&q2), 4, BarChars::default(), 1);
+        assert_eq!(
+            vec![
+                "Q1 [████] 100.00%",
+                "Q2 [██░░] 50.00%",
+                "Q3 [░░░░] 0.00%",
+                "Q4 [░░░░] 0.00%",
+            ],
+            bars.lines().collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn test_year_bars_for_october_fiscal_year() {
+        // Mid-November is in the first quarter of a year starting in October.
+        let t = DateTime::parse_from_rfc3339("1999-11-15T12:00:00+00:00").unwrap();
+        let coordinates = crate::generate_fiscal_coordinates(&t, 10);
+        let bars = render_year_bars(&coordinates, 4, BarChars::default(), 0);
+        let lines: Vec<&str> = bars.lines().collect();
+        assert!(lines[0].starts_with("Q0 [██░░]"));
+        assert_eq!(
+            vec!["Q1 [░░░░] 0.00%", "Q2 [░░░░] 0.00%", "Q3 [░░░░] 0.00%"],
+            lines[1..]
+        );
+    }
+
+    #[test]
+    fn test_group_thousands() {
+        assert_eq!("0", group_thousands(0));
+        assert_eq!("999", group_thousands(999));
+        assert_eq!("1,000", group_thousands(1000));
+        assert_eq!("90,000", group_thousands(90000));
+        assert_eq!("5,184,000", group_thousands(5184000));
+    }
+}
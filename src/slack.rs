@@ -0,0 +1,46 @@
+use std::{error, fmt, io};
+
+use crate::CorporateCoordinates;
+
+const POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+
+#[derive(Debug)]
+pub enum SlackError {
+    Http(Box<ureq::Error>),
+    Io(io::Error),
+    Api(String),
+}
+
+impl fmt::Display for SlackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlackError::Http(e) => write!(f, "could not reach Slack: {}", e),
+            SlackError::Io(e) => write!(f, "could not read Slack's response: {}", e),
+            SlackError::Api(response) => write!(f, "Slack rejected the message: {}", response),
+        }
+    }
+}
+
+impl error::Error for SlackError {}
+
+/// Posts the coordinates' Block Kit summary to `channel` with the Slack Web
+/// API's `chat.postMessage`, authenticating with the bot or user `token`.
+pub fn post_to_channel(
+    coordinates: &CorporateCoordinates,
+    channel: &str,
+    token: &str,
+) -> Result<(), SlackError> {
+    let response = ureq::post(POST_MESSAGE_URL)
+        .set("Authorization", &format!("Bearer {}", token))
+        .set("Content-Type", "application/json; charset=utf-8")
+        .send_string(&coordinates.to_slack_message(channel))
+        .map_err(|e| SlackError::Http(Box::new(e)))?
+        .into_string()
+        .map_err(SlackError::Io)?;
+    // Slack reports failures with a 200 and `"ok":false` in the body.
+    if response.contains("\"ok\":true") {
+        Ok(())
+    } else {
+        Err(SlackError::Api(response))
+    }
+}
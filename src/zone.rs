@@ -0,0 +1,72 @@
+use chrono::prelude::*;
+use chrono_tz::Tz;
+
+use crate::CorporateCoordinates;
+
+/// A quarter boundary whose fixed offset disagrees with its time zone.
+#[derive(Debug, PartialEq)]
+pub struct OffsetMismatch {
+    pub boundary: &'static str,
+    pub time: DateTime<FixedOffset>,
+    pub expected: FixedOffset,
+}
+
+/// Checks the offsets of the quarter's start and end against what `zone`
+/// reports for those local times, e.g. across a daylight saving change.
+pub fn verify_boundary_offsets(
+    coordinates: &CorporateCoordinates,
+    zone: Tz,
+) -> Vec<OffsetMismatch> {
+    [
+        ("start_of_quarter", coordinates.start_of_quarter),
+        ("end_of_quarter", coordinates.end_of_quarter),
+    ]
+    .into_iter()
+    .filter_map(|(boundary, time)| {
+        let expected = zone
+            .from_local_datetime(&time.naive_local())
+            .earliest()?
+            .fixed_offset()
+            .timezone();
+        (expected != time.timezone()).then_some(OffsetMismatch {
+            boundary,
+            time,
+            expected,
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_coordinates;
+    use chrono_tz::America::New_York;
+
+    #[test]
+    fn test_boundaries_within_one_offset() {
+        let t = Utc
+            .with_ymd_and_hms(2025, 5, 16, 16, 0, 0)
+            .unwrap()
+            .with_timezone(&New_York)
+            .fixed_offset();
+        assert!(verify_boundary_offsets(&generate_coordinates(&t), New_York).is_empty());
+    }
+
+    #[test]
+    fn test_boundary_across_daylight_saving() {
+        // Daylight saving time started on 9th March, after Q1 began.
+        let t = Utc
+            .with_ymd_and_hms(2025, 3, 20, 16, 0, 0)
+            .unwrap()
+            .with_timezone(&New_York)
+            .fixed_offset();
+        let mismatches = verify_boundary_offsets(&generate_coordinates(&t), New_York);
+        assert_eq!(1, mismatches.len());
+        assert_eq!("start_of_quarter", mismatches[0].boundary);
+        assert_eq!(
+            FixedOffset::west_opt(5 * 3600).unwrap(),
+            mismatches[0].expected
+        );
+    }
+}
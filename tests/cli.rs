@@ -0,0 +1,141 @@
+use std::process::Command;
+
+#[test]
+fn test_silent_alert_threshold_only_sets_exit_code() {
+    let run = |threshold: &str| {
+        Command::new(env!("CARGO_BIN_EXE_corporateclock"))
+            .args(["--date", "1999-06-20T12:00:00+00:00", "--silent"])
+            .args(["--alert-threshold", threshold])
+            .output()
+            .unwrap()
+    };
+
+    // 11 of 90 days remain.
+    let alerting = run("20");
+    assert!(alerting.stdout.is_empty());
+    assert_eq!(Some(2), alerting.status.code());
+
+    let quiet = run("5");
+    assert!(quiet.stdout.is_empty());
+    assert_eq!(Some(0), quiet.status.code());
+}
+
+#[test]
+fn test_compact_summary_keeps_the_other_options() {
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_corporateclock"))
+            .args(["--date", "1999-05-16T12:00:00+00:00", "--no-color"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(
+        "Q2, 1999: 50.00% remaining, 45 days left.\n",
+        run(&["--compact"])
+    );
+    assert_eq!(run(&["--compact"]), run(&["--summary-style", "short"]));
+    assert_eq!(
+        concat!(
+            "Q2, 1999: 25.00% remaining, 15 days left before the deadline, ",
+            "as of 1999-05-16T12:00:00+00:00 (+00:00). The quarter is AT RISK.\n"
+        ),
+        run(&[
+            "--compact",
+            "--deadline",
+            "1999-05-31",
+            "--show-zone",
+            "--verbose"
+        ])
+    );
+}
+
+#[test]
+fn test_phase_weights_are_validated_before_output() {
+    for weights in ["0,0,0", "1,-2,3", "1,2"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_corporateclock"))
+            .args(["--date", "1999-05-16T12:00:00+00:00"])
+            .args(["--phase-weights", weights])
+            .output()
+            .unwrap();
+        assert!(output.stdout.is_empty(), "{}", weights);
+        assert_eq!(Some(2), output.status.code());
+    }
+}
+
+#[test]
+fn test_forecast_rate_is_validated_before_output() {
+    for rate in ["0", "-8.5"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_corporateclock"))
+            .args(["--date", "1999-05-16T12:00:00+00:00"])
+            .args(["--forecast-end-of-quarter", "500", rate])
+            .output()
+            .unwrap();
+        assert!(output.stdout.is_empty(), "{}", rate);
+        assert_eq!(Some(1), output.status.code());
+    }
+}
+
+#[test]
+fn test_silent_rollover_only_sets_exit_code() {
+    let cache = std::env::temp_dir().join(format!("corporateclock-test-{}", std::process::id()));
+    std::fs::create_dir_all(cache.join("corporateclock")).unwrap();
+    std::fs::write(
+        cache.join("corporateclock").join("last_run"),
+        "calendar=fiscal-1-end-year\nquarter=1999Q2\npercent_remaining=50\n",
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_corporateclock"))
+            .args(["--detect-rollover", "--silent"])
+            .args(args)
+            .env("XDG_CACHE_HOME", &cache)
+            .output()
+            .unwrap()
+    };
+    let rolled_over = run(&[]);
+    // The snapshot is now from this quarter, but in the calendar year, so
+    // it isn't compared against in a fiscal year.
+    let other_calendar = run(&["--fiscal-year-start", "10"]);
+    std::fs::remove_dir_all(&cache).unwrap();
+
+    assert!(rolled_over.stdout.is_empty());
+    assert_eq!(Some(3), rolled_over.status.code());
+    assert_eq!(Some(0), other_calendar.status.code());
+}
+
+#[test]
+fn test_echo_config_shows_effective_values() {
+    let output = Command::new(env!("CARGO_BIN_EXE_corporateclock"))
+        .args([
+            "--date",
+            "1999-05-16T12:00:00+00:00",
+            "--echo-config",
+            "--compact",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let row = |option: &str| {
+        stdout
+            .lines()
+            .find(|line| line.starts_with(option))
+            .map(|line| line.split_whitespace().skip(1).collect::<Vec<_>>())
+    };
+    assert_eq!(Some(vec!["short", "implied"]), row("--summary-style "));
+    assert_eq!(Some(vec!["true", "CLI"]), row("--compact "));
+}
+
+#[test]
+fn test_relative_quarter_is_rejected_for_months() {
+    let output = Command::new(env!("CARGO_BIN_EXE_corporateclock"))
+        .args(["--date", "1999-05-16T12:00:00+00:00"])
+        .args(["--period", "month", "--relative-quarter", "1"])
+        .output()
+        .unwrap();
+    assert!(output.stdout.is_empty());
+    assert_eq!(Some(1), output.status.code());
+}